@@ -1,5 +1,8 @@
 //! Utility module for handling Subspace client notifications.
 
+#[cfg(test)]
+mod tests;
+
 use parking_lot::Mutex;
 use sc_utils::mpsc::{TracingUnboundedReceiver, TracingUnboundedSender, tracing_unbounded};
 use std::fmt;
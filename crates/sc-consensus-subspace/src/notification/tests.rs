@@ -0,0 +1,59 @@
+use crate::notification::channel;
+use futures::StreamExt;
+use futures::executor::block_on;
+
+#[test]
+fn multiple_subscribers() {
+    let (sender, stream) = channel::<u32>("test_notification_stream");
+
+    // Notifications without subscribers are dropped and value is not even created
+    sender.notify(|| unreachable!("No subscribers"));
+
+    let mut early_subscriber = stream.subscribe();
+    sender.notify(|| 1);
+
+    // Subscribers can be added at any time through any clone of the stream
+    let mut late_subscriber = stream.clone().subscribe();
+    // Subscriber that never reads its notifications doesn't block others
+    let idle_subscriber = stream.subscribe();
+    sender.notify(|| 2);
+    sender.notify(|| 3);
+
+    assert_eq!(block_on(early_subscriber.next()), Some(1));
+    assert_eq!(block_on(early_subscriber.next()), Some(2));
+    assert_eq!(block_on(early_subscriber.next()), Some(3));
+    // Late subscriber only receives notifications sent after subscription
+    assert_eq!(block_on(late_subscriber.next()), Some(2));
+    assert_eq!(block_on(late_subscriber.next()), Some(3));
+
+    // Dropped subscribers are pruned on the next notification
+    drop(idle_subscriber);
+    drop(early_subscriber);
+    sender.notify(|| 4);
+    assert_eq!(sender.subscribers.lock().len(), 1);
+    assert_eq!(block_on(late_subscriber.next()), Some(4));
+
+    // Stream ends once sender is gone
+    drop(sender);
+    drop(stream);
+    assert_eq!(block_on(late_subscriber.next()), None);
+}
+
+#[test]
+fn slow_subscriber_receives_everything_in_order() {
+    let (sender, stream) = channel::<u32>("test_notification_stream");
+
+    let subscriber = stream.subscribe();
+
+    // Subscriber channels are unbounded, queue size above warning threshold doesn't drop anything
+    for value in 0..1_000 {
+        sender.notify(|| value);
+    }
+    drop(sender);
+    drop(stream);
+
+    assert_eq!(
+        block_on(subscriber.collect::<Vec<_>>()),
+        (0..1_000).collect::<Vec<_>>()
+    );
+}
@@ -9,7 +9,7 @@ use alloc::vec::Vec;
 use rayon::prelude::*;
 use subspace_core_primitives::hashes::blake3_254_hash_to_scalar;
 use subspace_core_primitives::pieces::{Piece, RawRecord};
-use subspace_core_primitives::segments::ArchivedHistorySegment;
+use subspace_core_primitives::segments::{ArchivedHistorySegment, RecordedHistorySegment};
 use subspace_erasure_coding::ErasureCoding;
 use subspace_kzg::{Commitment, Kzg, Polynomial, Scalar};
 
@@ -20,6 +20,19 @@ pub enum ReconstructorError {
     #[error("Error during data shards reconstruction: {0}")]
     DataShardsReconstruction(String),
 
+    /// Not enough pieces are available to reconstruct the segment.
+    #[error(
+        "Not enough pieces to reconstruct: {available} available, {required} required \
+        ({} more needed)",
+        .required - .available
+    )]
+    NotEnoughPieces {
+        /// Number of pieces available
+        available: usize,
+        /// Number of pieces required for reconstruction
+        required: usize,
+    },
+
     /// Commitment of input piece is invalid.
     #[error("Commitment of input piece is invalid.")]
     InvalidInputPieceCommitment,
@@ -53,6 +66,16 @@ impl PiecesReconstructor {
         &self,
         input_pieces: &[Option<Piece>],
     ) -> Result<(ArchivedHistorySegment, Polynomial), ReconstructorError> {
+        // Any half of all pieces is sufficient due to erasure coding
+        let available = input_pieces.iter().flatten().count();
+        let required = RecordedHistorySegment::NUM_RAW_RECORDS;
+        if available < required {
+            return Err(ReconstructorError::NotEnoughPieces {
+                available,
+                required,
+            });
+        }
+
         let mut reconstructed_pieces = ArchivedHistorySegment::default();
 
         // Scratch buffer to avoid re-allocation
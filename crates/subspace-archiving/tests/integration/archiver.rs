@@ -8,14 +8,14 @@ use std::{assert_matches, iter};
 use subspace_archiving::archiver::{Archiver, ArchiverInstantiationError, SegmentItem};
 use subspace_core_primitives::hashes::Blake3Hash;
 use subspace_core_primitives::objects::{BlockObject, BlockObjectMapping, GlobalObject};
-use subspace_core_primitives::pieces::{Piece, Record};
+use subspace_core_primitives::pieces::{Piece, PieceIndex, Record};
 use subspace_core_primitives::segments::{
     ArchivedBlockProgress, ArchivedHistorySegment, LastArchivedBlock, RecordedHistorySegment,
     SegmentCommitment, SegmentHeader, SegmentIndex,
 };
 use subspace_erasure_coding::ErasureCoding;
 use subspace_kzg::Kzg;
-use subspace_verification::is_piece_valid;
+use subspace_verification::{is_piece_valid, verify_record};
 
 fn extract_data<O: Into<u32>>(data: &[u8], offset: O) -> &[u8] {
     let offset: u32 = offset.into();
@@ -703,3 +703,74 @@ fn object_on_the_edge_of_segment() {
         mapped_bytes
     );
 }
+
+#[test]
+fn record_verification() {
+    let kzg = Kzg::new();
+    let erasure_coding = ErasureCoding::new(
+        NonZeroUsize::new(Record::NUM_S_BUCKETS.next_power_of_two().ilog2() as usize)
+            .expect("Not zero; qed"),
+    )
+    .unwrap();
+    let mut archiver = Archiver::new(kzg.clone(), erasure_coding);
+
+    let block = {
+        let mut block = vec![0u8; RecordedHistorySegment::SIZE];
+        thread_rng().fill(block.as_mut_slice());
+        block
+    };
+    let archived_segments = archiver
+        .add_block(block, BlockObjectMapping::default(), true)
+        .archived_segments;
+    let archived_segment = archived_segments.first().unwrap();
+    let segment_commitment = archived_segment.segment_header.segment_commitment();
+    let segment_index = archived_segment.segment_header.segment_index();
+
+    for (piece_index, piece) in segment_index
+        .segment_piece_indexes()
+        .into_iter()
+        .zip(archived_segment.pieces.iter())
+        .step_by(16)
+    {
+        assert!(verify_record(
+            &kzg,
+            &segment_commitment,
+            piece_index,
+            piece.commitment(),
+            piece.witness(),
+        ));
+    }
+
+    let piece_index = segment_index.first_piece_index();
+    let piece = &archived_segment.pieces[0];
+
+    // Wrong position within segment
+    assert!(!verify_record(
+        &kzg,
+        &segment_commitment,
+        PieceIndex::from(u64::from(piece_index) + 1),
+        piece.commitment(),
+        piece.witness(),
+    ));
+
+    // Witness of a different piece
+    let other_piece = &archived_segment.pieces[1];
+    assert!(!verify_record(
+        &kzg,
+        &segment_commitment,
+        piece_index,
+        piece.commitment(),
+        other_piece.witness(),
+    ));
+
+    // Tampered witness
+    let mut tampered_piece = Piece::from(piece);
+    tampered_piece.witness_mut()[0] ^= 1;
+    assert!(!verify_record(
+        &kzg,
+        &segment_commitment,
+        piece_index,
+        tampered_piece.commitment(),
+        tampered_piece.witness(),
+    ));
+}
@@ -69,6 +69,52 @@ fn segment_reconstruction_works() {
         });
 }
 
+#[test]
+fn segment_reconstruction_from_half_of_pieces() {
+    let kzg = Kzg::new();
+    let erasure_coding = ErasureCoding::new(
+        NonZeroUsize::new(Record::NUM_S_BUCKETS.next_power_of_two().ilog2() as usize)
+            .expect("Not zero; qed"),
+    )
+    .unwrap();
+    let mut archiver = Archiver::new(kzg.clone(), erasure_coding.clone());
+
+    let block = get_random_block();
+
+    let archived_segments = archiver
+        .add_block(block, BlockObjectMapping::default(), true)
+        .archived_segments;
+
+    assert_eq!(archived_segments.len(), 1);
+
+    let archived_segment = archived_segments.into_iter().next().unwrap();
+    let mut maybe_pieces = pieces_to_option_of_pieces(&archived_segment.pieces);
+
+    // Remove exactly half of the pieces, which is still enough for reconstruction
+    maybe_pieces
+        .iter_mut()
+        .take(ArchivedHistorySegment::NUM_PIECES / 2)
+        .for_each(|piece| {
+            piece.take();
+        });
+
+    let reconstructor = PiecesReconstructor::new(kzg, erasure_coding);
+
+    let flat_pieces = reconstructor.reconstruct_segment(&maybe_pieces).unwrap();
+    assert_eq!(flat_pieces.len(), ArchivedHistorySegment::NUM_PIECES);
+    assert_eq!(&*flat_pieces, &*archived_segment.pieces);
+
+    // One more piece missing is too many
+    maybe_pieces[ArchivedHistorySegment::NUM_PIECES / 2].take();
+    assert_eq!(
+        reconstructor.reconstruct_segment(&maybe_pieces),
+        Err(ReconstructorError::NotEnoughPieces {
+            available: RecordedHistorySegment::NUM_RAW_RECORDS - 1,
+            required: RecordedHistorySegment::NUM_RAW_RECORDS,
+        })
+    );
+}
+
 #[test]
 fn piece_reconstruction_works() {
     let kzg = Kzg::new();
@@ -137,10 +183,13 @@ fn segment_reconstruction_fails() {
     assert!(result.is_err());
 
     if let Err(error) = result {
-        assert!(matches!(
+        assert_eq!(
             error,
-            ReconstructorError::DataShardsReconstruction(..)
-        ));
+            ReconstructorError::NotEnoughPieces {
+                available: 0,
+                required: RecordedHistorySegment::NUM_RAW_RECORDS,
+            }
+        );
     }
 
     let mut archiver = Archiver::new(kzg, erasure_coding);
@@ -180,10 +229,13 @@ fn piece_reconstruction_fails() {
     assert!(result.is_err());
 
     if let Err(error) = result {
-        assert!(matches!(
+        assert_eq!(
             error,
-            ReconstructorError::DataShardsReconstruction(..)
-        ));
+            ReconstructorError::NotEnoughPieces {
+                available: 0,
+                required: RecordedHistorySegment::NUM_RAW_RECORDS,
+            }
+        );
     }
 
     let mut archiver = Archiver::new(kzg, erasure_coding);
@@ -33,6 +33,7 @@ use serde::{Deserializer, Serializer};
     MaxEncodedLen,
     DecodeWithMemTracking,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Blake3Hash([u8; Blake3Hash::SIZE]);
 
 #[cfg(feature = "serde")]
@@ -156,6 +157,47 @@ pub fn blake3_hash_list(data: &[&[u8]]) -> Blake3Hash {
     state.finalize().as_bytes().into()
 }
 
+/// Incremental BLAKE3 hasher for data that is not available all at once.
+///
+/// Produces the same output as [`blake3_hash`] and [`blake3_hash_with_key`] when fed the same
+/// bytes, regardless of how they are split across [`Self::update`] calls.
+#[derive(Debug, Clone)]
+pub struct Blake3Hasher(blake3::Hasher);
+
+impl Default for Blake3Hasher {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Blake3Hasher {
+    /// Create new unkeyed hasher
+    #[inline]
+    pub fn new() -> Self {
+        Self(blake3::Hasher::new())
+    }
+
+    /// Create new keyed hasher
+    #[inline]
+    pub fn new_keyed(key: &[u8; 32]) -> Self {
+        Self(blake3::Hasher::new_keyed(key))
+    }
+
+    /// Add more data to the hasher
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.0.update(data);
+        self
+    }
+
+    /// Finalize hashing and return the hash
+    #[inline]
+    pub fn finalize(self) -> Blake3Hash {
+        self.0.finalize().as_bytes().into()
+    }
+}
+
 /// BLAKE3 hashing of a single value truncated to 254 bits as Scalar for usage with KZG.
 #[inline]
 pub fn blake3_254_hash_to_scalar(data: &[u8]) -> ScalarBytes {
@@ -14,10 +14,14 @@ pub mod pot;
 pub mod sectors;
 pub mod segments;
 pub mod solutions;
+#[cfg(feature = "proptest")]
+pub mod testing;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "serde")]
+pub mod u256;
 
-use crate::hashes::{Blake3Hash, blake3_hash, blake3_hash_list};
+use crate::hashes::{Blake3Hash, blake3_hash, blake3_hash_list, blake3_hash_with_key};
 use core::fmt;
 use derive_more::{Add, AsMut, AsRef, Deref, DerefMut, Display, Div, From, Into, Mul, Rem, Sub};
 use num_traits::{WrappingAdd, WrappingSub};
@@ -35,6 +39,9 @@ const_assert!(core::mem::size_of::<usize>() >= core::mem::size_of::<u32>());
 /// Signing context used for creating reward signatures by farmers.
 pub const REWARD_SIGNING_CONTEXT: &[u8] = b"subspace_reward";
 
+/// Context used for deriving randomness with [`Randomness::derive()`].
+pub const RANDOMNESS_CONTEXT: &[u8] = b"subspace_randomness";
+
 /// Type of randomness.
 #[derive(
     Default, Copy, Clone, Eq, PartialEq, From, Into, Deref, Encode, Decode, TypeInfo, MaxEncodedLen,
@@ -105,6 +112,18 @@ impl Randomness {
     /// Size of randomness (in bytes).
     pub const SIZE: usize = 32;
 
+    /// Derive randomness from arbitrary input.
+    ///
+    /// Input is hashed with a key derived from [`RANDOMNESS_CONTEXT`], so the result never collides
+    /// with a plain hash of any input, including the context concatenated with the input.
+    #[inline]
+    pub fn derive(input: &[u8]) -> Self {
+        Self(*blake3_hash_with_key(
+            &blake3_hash(RANDOMNESS_CONTEXT),
+            input,
+        ))
+    }
+
     /// Derive global slot challenge from global randomness.
     // TODO: Separate type for global challenge
     pub fn derive_global_challenge(&self, slot: SlotNumber) -> Blake3Hash {
@@ -144,6 +163,7 @@ pub type BlockForkWeight = u128;
     Into,
     DecodeWithMemTracking,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PublicKey([u8; PublicKey::SIZE]);
 
 impl fmt::Debug for PublicKey {
@@ -237,6 +257,7 @@ impl PublicKey {
     DecodeWithMemTracking,
 )]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(transparent))]
 pub struct ScalarBytes([u8; ScalarBytes::FULL_BYTES]);
 
@@ -480,3 +501,14 @@ impl Default for U256 {
         Self::zero()
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for U256 {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::from_le_bytes(u.arbitrary()?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[u8; 32] as arbitrary::Arbitrary<'a>>::size_hint(depth)
+    }
+}
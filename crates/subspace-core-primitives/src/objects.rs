@@ -9,6 +9,7 @@ use crate::pieces::PieceIndex;
 use core::default::Default;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
+use scale_info::prelude::collections::BTreeMap;
 use scale_info::prelude::vec;
 use scale_info::prelude::vec::Vec;
 #[cfg(feature = "serde")]
@@ -162,3 +163,67 @@ impl GlobalObjectMapping {
         }
     }
 }
+
+/// Index of objects stored in the history of the blockchain, allows to find pieces that contain
+/// object with a particular hash.
+///
+/// The same object can be stored in the history more than once, all of its locations are retained
+/// in the order of piece index and offset.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ObjectMappingIndex {
+    objects: BTreeMap<Blake3Hash, Vec<GlobalObject>>,
+}
+
+impl Extend<GlobalObject> for ObjectMappingIndex {
+    fn extend<T: IntoIterator<Item = GlobalObject>>(&mut self, iter: T) {
+        for object in iter {
+            self.insert(object);
+        }
+    }
+}
+
+impl FromIterator<GlobalObject> for ObjectMappingIndex {
+    fn from_iter<T: IntoIterator<Item = GlobalObject>>(iter: T) -> Self {
+        let mut index = Self::default();
+        index.extend(iter);
+        index
+    }
+}
+
+impl ObjectMappingIndex {
+    /// Create index from object mappings produced for a segment (or any other set of mappings)
+    pub fn from_mapping(mapping: &GlobalObjectMapping) -> Self {
+        mapping.objects().iter().copied().collect()
+    }
+
+    /// Insert object mapping into the index, duplicate mappings are ignored
+    pub fn insert(&mut self, object: GlobalObject) {
+        let locations = self.objects.entry(object.hash).or_default();
+        if let Err(position) = locations.binary_search(&object) {
+            locations.insert(position, object);
+        }
+    }
+
+    /// Get the first location of the object with specified hash
+    pub fn get(&self, hash: &Blake3Hash) -> Option<&GlobalObject> {
+        self.get_all(hash).first()
+    }
+
+    /// Get all locations of the object with specified hash
+    pub fn get_all(&self, hash: &Blake3Hash) -> &[GlobalObject] {
+        self.objects
+            .get(hash)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Number of distinct objects in the index
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Whether index is empty
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+}
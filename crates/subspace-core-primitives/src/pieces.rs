@@ -4,6 +4,7 @@
 extern crate alloc;
 
 use crate::ScalarBytes;
+use crate::hashes::{Blake3Hash, blake3_hash};
 use crate::segments::{ArchivedHistorySegment, RecordedHistorySegment, SegmentIndex};
 #[cfg(feature = "serde")]
 use ::serde::{Deserialize, Serialize};
@@ -15,6 +16,10 @@ use alloc::boxed::Box;
 use alloc::format;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
+#[cfg(feature = "text-encoding")]
+use base64::Engine;
+#[cfg(feature = "text-encoding")]
+use base64::engine::general_purpose::STANDARD as BASE64;
 use bytes::{Bytes, BytesMut};
 use core::array::TryFromSliceError;
 use core::hash::{Hash, Hasher};
@@ -24,6 +29,8 @@ use derive_more::with_trait::{
     Add, AddAssign, AsMut, AsRef, Deref, DerefMut, Display, Div, DivAssign, From, Into, Mul,
     MulAssign, Sub, SubAssign,
 };
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
 use parity_scale_codec::{
     Decode, DecodeWithMemTracking, Encode, EncodeLike, Input, MaxEncodedLen, Output,
 };
@@ -33,6 +40,10 @@ use scale_info::build::Fields;
 use scale_info::{Path, Type, TypeInfo};
 #[cfg(feature = "serde")]
 use serde_big_array::BigArray;
+#[cfg(feature = "mmap")]
+use std::fs::File;
+#[cfg(feature = "mmap")]
+use std::io;
 
 /// Piece index in consensus
 #[derive(
@@ -60,6 +71,7 @@ use serde_big_array::BigArray;
     MaxEncodedLen,
 )]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(transparent)]
 pub struct PieceIndex(u64);
 
@@ -225,6 +237,7 @@ impl PieceIndex {
     DecodeWithMemTracking,
 )]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(transparent)]
 pub struct PieceOffset(u16);
 
@@ -682,6 +695,7 @@ impl Record {
     MaxEncodedLen,
     DecodeWithMemTracking,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RecordCommitment([u8; RecordCommitment::SIZE]);
 
 impl fmt::Debug for RecordCommitment {
@@ -818,6 +832,7 @@ impl RecordCommitment {
     MaxEncodedLen,
     DecodeWithMemTracking,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RecordWitness([u8; RecordWitness::SIZE]);
 
 impl fmt::Debug for RecordWitness {
@@ -1221,9 +1236,24 @@ impl AsMut<[u8]> for Piece {
     }
 }
 
+/// Backing storage for [`Piece::zeroed()`]
+static ZERO_PIECE_BYTES: [u8; Piece::SIZE] = [0; Piece::SIZE];
+
 impl Piece {
     /// Size of a piece (in bytes).
     pub const SIZE: usize = Record::SIZE + RecordCommitment::SIZE + RecordWitness::SIZE;
+    /// Size of a source piece without zero padding of record chunks (in bytes), see
+    /// [`Self::to_safe_bytes()`].
+    pub const SAFE_SIZE: usize = RawRecord::SIZE + RecordCommitment::SIZE + RecordWitness::SIZE;
+
+    /// Piece with all bytes set to zero.
+    ///
+    /// Doesn't allocate, data is shared and copied on first mutable access. Useful for tests and
+    /// pre-allocated buffers, but it is not a valid archival piece and will not pass verification.
+    #[inline]
+    pub const fn zeroed() -> Self {
+        Self(CowBytes::Shared(Bytes::from_static(&ZERO_PIECE_BYTES)))
+    }
 
     /// Ensure piece contains cheaply cloneable shared data.
     ///
@@ -1236,6 +1266,113 @@ impl Piece {
             CowBytes::Owned(bytes) => CowBytes::Shared(bytes.freeze()),
         })
     }
+
+    /// Encode piece as base64 string (standard alphabet with padding).
+    #[cfg(feature = "text-encoding")]
+    pub fn to_base64(&self) -> String {
+        BASE64.encode(self)
+    }
+
+    /// Decode piece from base64 string (standard alphabet with padding).
+    #[cfg(feature = "text-encoding")]
+    pub fn from_base64(s: &str) -> Result<Self, PieceDecodingError> {
+        let bytes = BASE64
+            .decode(s)
+            .map_err(|error| PieceDecodingError::InvalidBase64(error.to_string()))?;
+
+        Self::from_decoded_bytes(bytes)
+    }
+
+    /// Compact representation of a source piece with zero padding of record chunks removed.
+    ///
+    /// Record chunks of source pieces store [`ScalarBytes::SAFE_BYTES`] bytes of data each, padded
+    /// with a zero byte to [`ScalarBytes::FULL_BYTES`]. Parity pieces use full chunks and can't be
+    /// converted, [`SafeBytesError::NonZeroPadding`] is returned for them.
+    pub fn to_safe_bytes(&self) -> Result<Vec<u8>, SafeBytesError> {
+        let record = self.record();
+        if let Some(chunk) = record.iter().position(|bytes| bytes[0] != 0) {
+            return Err(SafeBytesError::NonZeroPadding { chunk });
+        }
+
+        let mut bytes = Vec::with_capacity(Self::SAFE_SIZE);
+        for chunk in record.to_raw_record_chunks() {
+            bytes.extend_from_slice(chunk);
+        }
+        bytes.extend_from_slice(self.commitment().as_slice());
+        bytes.extend_from_slice(self.witness().as_slice());
+
+        Ok(bytes)
+    }
+
+    /// Restore source piece from its compact representation created with
+    /// [`Self::to_safe_bytes()`].
+    pub fn from_safe_bytes(bytes: &[u8]) -> Result<Self, SafeBytesError> {
+        if bytes.len() != Self::SAFE_SIZE {
+            return Err(SafeBytesError::InvalidLength {
+                expected: Self::SAFE_SIZE,
+                actual: bytes.len(),
+            });
+        }
+
+        let (raw_record, rest) = bytes.split_at(RawRecord::SIZE);
+        let (commitment, witness) = rest.split_at(RecordCommitment::SIZE);
+
+        let mut piece = Self::default();
+        piece
+            .record_mut()
+            .to_mut_raw_record_chunks()
+            .zip(raw_record.as_chunks::<{ ScalarBytes::SAFE_BYTES }>().0)
+            .for_each(|(output, input)| output.copy_from_slice(input));
+        piece.commitment_mut().copy_from_slice(commitment);
+        piece.witness_mut().copy_from_slice(witness);
+
+        Ok(piece)
+    }
+
+    #[cfg(feature = "text-encoding")]
+    fn from_decoded_bytes(bytes: Vec<u8>) -> Result<Self, PieceDecodingError> {
+        let actual = bytes.len();
+        Self::try_from(bytes).map_err(|()| PieceDecodingError::InvalidLength {
+            expected: Self::SIZE,
+            actual,
+        })
+    }
+}
+
+/// Error returned by [`Piece::to_safe_bytes()`] and [`Piece::from_safe_bytes()`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum SafeBytesError {
+    /// Record chunk has non-zero padding byte, which means this is not a source piece
+    #[error("Record chunk {chunk} has non-zero padding, only source pieces can be converted")]
+    NonZeroPadding {
+        /// Index of the first chunk with non-zero padding
+        chunk: usize,
+    },
+    /// Input has wrong length
+    #[error("Invalid safe bytes length: expected {expected} bytes, got {actual}")]
+    InvalidLength {
+        /// Expected length
+        expected: usize,
+        /// Actual length
+        actual: usize,
+    },
+}
+
+/// Error returned when decoding [`Piece`] from a string
+#[cfg(feature = "text-encoding")]
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum PieceDecodingError {
+    /// String is not valid base64
+    #[error("Invalid base64 string: {0}")]
+    InvalidBase64(String),
+    /// Decoded bytes have wrong length
+    #[error("Invalid piece length: expected {expected} bytes, got {actual}")]
+    InvalidLength {
+        /// Expected length
+        expected: usize,
+        /// Actual length
+        actual: usize,
+    },
 }
 
 /// A piece of archival history in Subspace Network.
@@ -1258,7 +1395,7 @@ impl fmt::Debug for PieceArray {
 impl Default for PieceArray {
     #[inline]
     fn default() -> Self {
-        Self([0u8; Piece::SIZE])
+        Self::zeroed()
     }
 }
 
@@ -1313,6 +1450,15 @@ impl From<&mut [u8; Piece::SIZE]> for &mut PieceArray {
 }
 
 impl PieceArray {
+    /// Piece with all bytes set to zero.
+    ///
+    /// Value is large, prefer [`Self::new_boxed()`] for heap allocation. Not a valid archival piece
+    /// and will not pass verification.
+    #[inline]
+    pub const fn zeroed() -> Self {
+        Self([0; Piece::SIZE])
+    }
+
     /// Create boxed value without hitting stack overflow
     #[inline]
     pub fn new_boxed() -> Box<Self> {
@@ -1389,6 +1535,21 @@ impl PieceArray {
         self.split_mut().2
     }
 
+    /// Checksum of piece bytes.
+    ///
+    /// This is a fast check for local corruption (for example of pieces stored on disk) and is not
+    /// a replacement for verifying piece against segment commitment.
+    #[inline]
+    pub fn checksum(&self) -> Blake3Hash {
+        blake3_hash(&self.0)
+    }
+
+    /// Check whether piece bytes match previously computed [`Self::checksum()`].
+    #[inline]
+    pub fn verify_checksum(&self, expected: &Blake3Hash) -> bool {
+        &self.checksum() == expected
+    }
+
     /// Convenient conversion from slice of piece array to underlying representation for efficiency
     /// purposes.
     #[inline]
@@ -1479,6 +1640,19 @@ impl DerefMut for FlatPieces {
     }
 }
 
+impl TryFrom<Vec<Piece>> for FlatPieces {
+    type Error = ();
+
+    #[inline]
+    fn try_from(pieces: Vec<Piece>) -> Result<Self, Self::Error> {
+        if pieces.is_empty() {
+            return Err(());
+        }
+
+        Ok(Self::from_pieces(pieces.into_iter()))
+    }
+}
+
 impl FlatPieces {
     /// Allocate `FlatPieces` that will hold `piece_count` pieces filled with zeroes
     #[inline]
@@ -1486,6 +1660,24 @@ impl FlatPieces {
         Self(CowBytes::Owned(BytesMut::zeroed(piece_count * Piece::SIZE)))
     }
 
+    /// Create `FlatPieces` from an iterator of pieces, flat buffer is allocated once upfront.
+    ///
+    /// # Panics
+    /// Panics if iterator is empty.
+    pub fn from_pieces<Iter>(pieces: Iter) -> Self
+    where
+        Iter: ExactSizeIterator<Item = Piece>,
+    {
+        assert_ne!(pieces.len(), 0, "Can't create flat pieces from zero pieces");
+
+        let mut bytes = BytesMut::with_capacity(pieces.len() * Piece::SIZE);
+        for piece in pieces {
+            bytes.extend_from_slice(piece.as_ref());
+        }
+
+        Self(CowBytes::Owned(bytes))
+    }
+
     /// Iterate over all pieces.
     ///
     /// NOTE: Unless [`Self::to_shared`] was called first, iterator may have to allocate each piece
@@ -1555,8 +1747,47 @@ impl FlatPieces {
     }
 }
 
+#[cfg(feature = "mmap")]
+impl FlatPieces {
+    /// Create `FlatPieces` backed by memory-mapped file contents without copying them into memory.
+    ///
+    /// File length must be a multiple of [`Piece::SIZE`]. Pieces are read lazily by the OS as they
+    /// are accessed, [`Self::pieces()`] returns pieces that reference mapped memory directly.
+    /// Mutable access to pieces copies the whole mapping into memory first.
+    ///
+    /// # Safety
+    /// File must not be truncated or modified (by this or any other process) for as long as
+    /// returned instance or any pieces derived from it are alive, otherwise reading pieces is
+    /// undefined behavior.
+    pub unsafe fn from_mmap(file: &File) -> io::Result<Self> {
+        let file_size = file.metadata()?.len();
+        if file_size % Piece::SIZE as u64 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "File size {file_size} is not a multiple of piece size {}",
+                    Piece::SIZE
+                ),
+            ));
+        }
+
+        // SAFETY: Caller guarantees file is not truncated or modified concurrently
+        let mmap = unsafe { Mmap::map(file)? };
+
+        Ok(Self(CowBytes::Shared(Bytes::from_owner(mmap))))
+    }
+}
+
 #[cfg(feature = "parallel")]
 impl FlatPieces {
+    /// Parallel iterator over all pieces in index order.
+    ///
+    /// Unlike [`Self::pieces()`] this doesn't allocate and yields references to pieces instead.
+    #[inline]
+    pub fn par_pieces(&self) -> impl IndexedParallelIterator<Item = &'_ PieceArray> + '_ {
+        self.par_iter()
+    }
+
     /// Parallel iterator over source pieces (even indices)
     #[inline]
     pub fn par_source(&self) -> impl IndexedParallelIterator<Item = &'_ PieceArray> + '_ {
@@ -43,6 +43,7 @@ impl PosSeed {
     MaxEncodedLen,
     DecodeWithMemTracking,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PosProof([u8; PosProof::SIZE]);
 
 impl fmt::Debug for PosProof {
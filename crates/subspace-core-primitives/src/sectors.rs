@@ -68,7 +68,11 @@ impl SectorId {
     }
 
     /// Derive piece index that should be stored in sector at `piece_offset` for specified size of
-    /// blockchain history
+    /// blockchain history.
+    ///
+    /// Piece index is derived by reducing 256-bit hash modulo number of pieces, which never
+    /// exceeds 2^64, so modulo bias is below 2^-192 and negligible. This is part of consensus, any
+    /// change here changes pieces stored in all existing sectors.
     pub fn derive_piece_index(
         &self,
         piece_offset: PieceOffset,
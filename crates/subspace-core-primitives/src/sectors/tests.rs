@@ -1,8 +1,105 @@
-use crate::pieces::Record;
-use crate::sectors::SBucket;
+use crate::hashes::blake3_hash;
+use crate::pieces::{PieceIndex, PieceOffset, Record};
+use crate::sectors::{SBucket, SectorId};
+use crate::segments::HistorySize;
+use core::num::NonZeroU64;
 
 // Statically validate that we can store all possible s-buckets in SBucket data structure
 #[test]
 fn s_buckets_fit_into_data_structure() {
     assert!((SBucket::ZERO..=SBucket(u16::MAX)).count() <= Record::NUM_S_BUCKETS);
 }
+
+fn history_size(segments: u64) -> HistorySize {
+    HistorySize::new(NonZeroU64::new(segments).unwrap())
+}
+
+#[test]
+fn sector_id_derivation() {
+    let public_key_hash = blake3_hash(b"public key");
+    let sector_index = 1;
+    let max_pieces_in_sector = 1000;
+    let recent_segments = history_size(5);
+    let recent_history_fraction = (history_size(1), history_size(10));
+
+    let sector_id = SectorId::new(public_key_hash, sector_index, history_size(100));
+    // Deterministic
+    assert_eq!(
+        sector_id,
+        SectorId::new(public_key_hash, sector_index, history_size(100))
+    );
+    // All inputs are taken into account
+    assert_ne!(
+        sector_id,
+        SectorId::new(public_key_hash, sector_index + 1, history_size(100))
+    );
+    assert_ne!(
+        sector_id,
+        SectorId::new(blake3_hash(b"other key"), sector_index, history_size(100))
+    );
+
+    // Sector created with the same public key and index at a different history size is a
+    // different sector that stores different pieces
+    let other_sector_id = SectorId::new(public_key_hash, sector_index, history_size(101));
+    assert_ne!(sector_id, other_sector_id);
+
+    let piece_indexes = |sector_id: &SectorId| {
+        (PieceOffset::ZERO..PieceOffset::from(max_pieces_in_sector))
+            .map(|piece_offset| {
+                sector_id.derive_piece_index(
+                    piece_offset,
+                    history_size(100),
+                    max_pieces_in_sector,
+                    recent_segments,
+                    recent_history_fraction,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(piece_indexes(&sector_id), piece_indexes(&sector_id));
+    assert_ne!(piece_indexes(&sector_id), piece_indexes(&other_sector_id));
+    assert!(
+        piece_indexes(&sector_id)
+            .into_iter()
+            .all(|piece_index| piece_index < PieceIndex::from(history_size(100).in_pieces().get()))
+    );
+}
+
+#[test]
+fn derive_piece_index_distribution() {
+    const NUM_SECTORS: u16 = 200;
+    const PIECES_IN_SECTOR: u16 = 1000;
+
+    // Single segment of history, recent history policy is not used for such a small history
+    let history = history_size(1);
+    let num_pieces = history.in_pieces().get() as usize;
+    let mut counts = vec![0_u64; num_pieces];
+
+    for sector_index in 0..NUM_SECTORS {
+        let sector_id = SectorId::new(blake3_hash(b"public key"), sector_index, history);
+        for piece_offset in PieceOffset::ZERO..PieceOffset::from(PIECES_IN_SECTOR) {
+            let piece_index = sector_id.derive_piece_index(
+                piece_offset,
+                history,
+                PIECES_IN_SECTOR,
+                history,
+                (history_size(1), history_size(10)),
+            );
+            counts[u64::from(piece_index) as usize] += 1;
+        }
+    }
+
+    // Pearson's chi-squared statistic for uniform distribution has `num_pieces - 1` degrees of
+    // freedom, so its mean is ~255 and standard deviation ~22.6, 400 is more than 6 standard
+    // deviations away
+    let samples = u64::from(NUM_SECTORS) * u64::from(PIECES_IN_SECTOR);
+    let expected = samples as f64 / num_pieces as f64;
+    let chi_squared = counts
+        .iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum::<f64>();
+    assert!(
+        chi_squared < 400.0,
+        "Piece index distribution is biased, chi-squared {chi_squared}"
+    );
+}
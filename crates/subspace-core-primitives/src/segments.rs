@@ -11,7 +11,8 @@ use alloc::boxed::Box;
 use core::array::TryFromSliceError;
 use core::fmt;
 use core::iter::Step;
-use core::num::NonZeroU64;
+use core::num::{NonZeroU64, ParseIntError};
+use core::str::FromStr;
 use derive_more::{
     Add, AddAssign, Deref, DerefMut, Display, Div, DivAssign, From, Into, Mul, MulAssign, Sub,
     SubAssign,
@@ -54,6 +55,7 @@ use serde_big_array::BigArray;
     DecodeWithMemTracking,
 )]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(transparent)]
 pub struct SegmentIndex(u64);
 
@@ -74,11 +76,23 @@ impl Step for SegmentIndex {
     }
 }
 
+impl FromStr for SegmentIndex {
+    type Err = ParseIntError;
+
+    /// Parses decimal segment index, negative and too large numbers are rejected
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str(s).map(Self)
+    }
+}
+
 impl SegmentIndex {
     /// Segment index 0.
     pub const ZERO: SegmentIndex = SegmentIndex(0);
     /// Segment index 1.
     pub const ONE: SegmentIndex = SegmentIndex(1);
+    /// Number of pieces in each segment, same as [`ArchivedHistorySegment::NUM_PIECES`].
+    pub const NUM_PIECES: u64 = ArchivedHistorySegment::NUM_PIECES as u64;
 
     /// Create new instance
     #[inline]
@@ -89,19 +103,25 @@ impl SegmentIndex {
     /// Get the first piece index in this segment.
     #[inline]
     pub const fn first_piece_index(&self) -> PieceIndex {
-        PieceIndex::new(self.0 * ArchivedHistorySegment::NUM_PIECES as u64)
+        PieceIndex::new(self.0 * Self::NUM_PIECES)
     }
 
     /// Get the last piece index in this segment.
     #[inline]
     pub const fn last_piece_index(&self) -> PieceIndex {
-        PieceIndex::new((self.0 + 1) * ArchivedHistorySegment::NUM_PIECES as u64 - 1)
+        PieceIndex::new((self.0 + 1) * Self::NUM_PIECES - 1)
+    }
+
+    /// Iterator over piece indexes that belong to this segment.
+    #[inline]
+    pub fn piece_indexes(&self) -> impl Iterator<Item = PieceIndex> + use<> {
+        self.first_piece_index()..=self.last_piece_index()
     }
 
     /// List of piece indexes that belong to this segment.
     pub fn segment_piece_indexes(&self) -> [PieceIndex; ArchivedHistorySegment::NUM_PIECES] {
         let mut piece_indices = [PieceIndex::ZERO; ArchivedHistorySegment::NUM_PIECES];
-        (self.first_piece_index()..=self.last_piece_index())
+        self.piece_indexes()
             .zip(&mut piece_indices)
             .for_each(|(input, output)| {
                 *output = input;
@@ -129,6 +149,22 @@ impl SegmentIndex {
         source_first_piece_indices
     }
 
+    /// Next segment index, returning `None` if overflow occurred.
+    #[inline]
+    pub const fn next(self) -> Option<Self> {
+        self.checked_add(Self::ONE)
+    }
+
+    /// Checked integer addition. Computes `self + rhs`, returning `None` if overflow occurred.
+    #[inline]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        // TODO: when Option::map becomes const, use it here
+        match self.0.checked_add(rhs.0) {
+            Some(segment_index) => Some(Self(segment_index)),
+            None => None,
+        }
+    }
+
     /// Checked integer subtraction. Computes `self - rhs`, returning `None` if underflow occurred.
     #[inline]
     pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
@@ -164,6 +200,7 @@ impl SegmentIndex {
     MaxEncodedLen,
     DecodeWithMemTracking,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(transparent)]
 pub struct SegmentCommitment([u8; SegmentCommitment::SIZE]);
 
@@ -270,6 +307,7 @@ impl SegmentCommitment {
     DecodeWithMemTracking,
 )]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[repr(transparent)]
 pub struct HistorySize(NonZeroU64);
 
@@ -309,6 +347,17 @@ impl HistorySize {
     }
 }
 
+/// Error returned by [`ArchivedBlockProgress::add_partial()`] on overflow of partially archived
+/// bytes
+#[derive(Debug, Copy, Clone, Eq, PartialEq, thiserror::Error)]
+#[error("Partially archived bytes overflow: {partial} + {bytes} doesn't fit into u32")]
+pub struct ProgressOverflow {
+    /// Number of partially archived bytes before addition
+    pub partial: u32,
+    /// Number of bytes that was attempted to be added
+    pub bytes: u32,
+}
+
 /// Progress of an archived block.
 #[derive(
     Debug,
@@ -325,6 +374,7 @@ impl HistorySize {
     DecodeWithMemTracking,
 )]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum ArchivedBlockProgress {
     /// The block has been fully archived.
@@ -356,6 +406,32 @@ impl ArchivedBlockProgress {
     pub fn set_partial(&mut self, new_partial: u32) {
         *self = Self::Partial(new_partial);
     }
+
+    /// Adds newly archived bytes to the number of partially archived bytes.
+    ///
+    /// [`Self::Complete`] is treated as zero bytes archived so far (start of a new block). Progress
+    /// is not changed on overflow. Transition to [`Self::Complete`] is up to the caller once the
+    /// whole block was archived.
+    pub fn add_partial(&mut self, bytes: u32) -> Result<(), ProgressOverflow> {
+        let partial = self.partial().unwrap_or_default();
+        let new_partial = partial
+            .checked_add(bytes)
+            .ok_or(ProgressOverflow { partial, bytes })?;
+        self.set_partial(new_partial);
+
+        Ok(())
+    }
+
+    /// Number of bytes of a block of size `block_size` that remain to be archived.
+    ///
+    /// Returns zero for [`Self::Complete`] and `None` if more bytes were archived than block
+    /// contains.
+    pub fn remaining(&self, block_size: u32) -> Option<u32> {
+        match self {
+            Self::Complete => Some(0),
+            Self::Partial(number) => block_size.checked_sub(*number),
+        }
+    }
 }
 
 /// Last archived block
@@ -374,6 +450,7 @@ impl ArchivedBlockProgress {
     DecodeWithMemTracking,
 )]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct LastArchivedBlock {
     /// Block number
@@ -409,6 +486,7 @@ impl LastArchivedBlock {
     Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Hash, DecodeWithMemTracking,
 )]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum SegmentHeader {
     /// V0 of the segment header data structure
@@ -424,6 +502,21 @@ pub enum SegmentHeader {
         /// Last archived block
         last_archived_block: LastArchivedBlock,
     },
+    /// V1 of the segment header data structure, additionally records archiver parameters
+    #[codec(index = 1)]
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    V1 {
+        /// Segment index
+        segment_index: SegmentIndex,
+        /// Root of commitments of all records in a segment.
+        segment_commitment: SegmentCommitment,
+        /// Hash of the segment header of the previous segment
+        prev_segment_header_hash: Blake3Hash,
+        /// Last archived block
+        last_archived_block: LastArchivedBlock,
+        /// Number of source records in a segment used by the archiver before erasure coding
+        record_count: u32,
+    },
 }
 
 impl SegmentHeader {
@@ -435,7 +528,7 @@ impl SegmentHeader {
     /// Segment index
     pub fn segment_index(&self) -> SegmentIndex {
         match self {
-            Self::V0 { segment_index, .. } => *segment_index,
+            Self::V0 { segment_index, .. } | Self::V1 { segment_index, .. } => *segment_index,
         }
     }
 
@@ -444,6 +537,9 @@ impl SegmentHeader {
         match self {
             Self::V0 {
                 segment_commitment, ..
+            }
+            | Self::V1 {
+                segment_commitment, ..
             } => *segment_commitment,
         }
     }
@@ -454,6 +550,10 @@ impl SegmentHeader {
             Self::V0 {
                 prev_segment_header_hash,
                 ..
+            }
+            | Self::V1 {
+                prev_segment_header_hash,
+                ..
             } => *prev_segment_header_hash,
         }
     }
@@ -464,9 +564,83 @@ impl SegmentHeader {
             Self::V0 {
                 last_archived_block,
                 ..
+            }
+            | Self::V1 {
+                last_archived_block,
+                ..
             } => *last_archived_block,
         }
     }
+
+    /// Number of source records in a segment used by the archiver, `None` for segment headers that
+    /// predate this information
+    pub fn record_count(&self) -> Option<u32> {
+        match self {
+            Self::V0 { .. } => None,
+            Self::V1 { record_count, .. } => Some(*record_count),
+        }
+    }
+
+    /// Number of the last block (fully or partially) archived in this segment
+    pub fn last_block_number(&self) -> BlockNumber {
+        self.last_archived_block().number
+    }
+
+    /// Whether the last block archived in this segment was archived fully
+    pub fn is_block_fully_archived(&self) -> bool {
+        self.last_archived_block().archived_progress == ArchivedBlockProgress::Complete
+    }
+
+    /// Number of partially archived bytes of the last block archived in this segment, `None` if it
+    /// was archived fully
+    pub fn partial_archived(&self) -> Option<u32> {
+        self.last_archived_block().partial_archived()
+    }
+}
+
+/// Error returned by [`verify_chain`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum SegmentHeaderChainError {
+    /// Segment header doesn't reference hash of the previous segment header
+    #[error("Segment header at position {position} doesn't reference previous segment header")]
+    HashMismatch {
+        /// Position of the segment header in the list
+        position: usize,
+    },
+    /// Segment index doesn't follow segment index of the previous segment header
+    #[error("Segment header at position {position} has index {actual}, expected {expected}")]
+    IndexGap {
+        /// Position of the segment header in the list
+        position: usize,
+        /// Expected segment index
+        expected: SegmentIndex,
+        /// Actual segment index
+        actual: SegmentIndex,
+    },
+}
+
+/// Verify that segment headers form a valid chain.
+///
+/// Each segment header must reference hash of the previous segment header and have segment index
+/// one larger than the previous one. The first segment header is not checked against anything.
+pub fn verify_chain(headers: &[SegmentHeader]) -> Result<(), SegmentHeaderChainError> {
+    for (position, [prev, header]) in headers.array_windows().enumerate() {
+        let position = position + 1;
+
+        if header.segment_index().checked_sub(prev.segment_index()) != Some(SegmentIndex::ONE) {
+            return Err(SegmentHeaderChainError::IndexGap {
+                position,
+                expected: prev.segment_index() + SegmentIndex::ONE,
+                actual: header.segment_index(),
+            });
+        }
+
+        if header.prev_segment_header_hash() != prev.hash() {
+            return Err(SegmentHeaderChainError::HashMismatch { position });
+        }
+    }
+
+    Ok(())
 }
 
 /// Recorded history segment before archiving is applied.
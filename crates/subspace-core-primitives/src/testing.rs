@@ -0,0 +1,147 @@
+//! Strategies for property-based testing of data structures in this crate with [`proptest`].
+//!
+//! All strategies produce structurally valid values that can be encoded and decoded, but they are
+//! not necessarily valid from consensus point of view (for example solutions will not verify).
+
+#[cfg(test)]
+mod tests;
+
+use crate::hashes::Blake3Hash;
+use crate::pieces::{PieceOffset, RecordCommitment, RecordWitness};
+use crate::pos::PosProof;
+use crate::segments::{
+    ArchivedBlockProgress, HistorySize, LastArchivedBlock, SegmentCommitment, SegmentHeader,
+    SegmentIndex,
+};
+use crate::solutions::{ChunkWitness, Solution};
+use crate::{PublicKey, ScalarBytes, U256};
+use core::fmt;
+use core::num::NonZeroU64;
+use proptest::prelude::*;
+
+/// Strategy for arbitrary byte arrays of any size
+pub fn arb_bytes<const N: usize>() -> impl Strategy<Value = [u8; N]> {
+    proptest::collection::vec(any::<u8>(), N)
+        .prop_map(|bytes| <[u8; N]>::try_from(bytes).expect("Vector has correct length; qed"))
+}
+
+/// Strategy for [`U256`] covering the full range of values
+pub fn arb_u256() -> impl Strategy<Value = U256> {
+    arb_bytes::<32>().prop_map(U256::from_le_bytes)
+}
+
+/// Strategy for [`PublicKey`]
+pub fn arb_public_key() -> impl Strategy<Value = PublicKey> {
+    arb_bytes::<{ PublicKey::SIZE }>().prop_map(PublicKey::from)
+}
+
+/// Strategy for [`ScalarBytes`] that always contain a valid BLS12-381 scalar (254 bits)
+pub fn arb_scalar_bytes() -> impl Strategy<Value = ScalarBytes> {
+    arb_bytes::<{ ScalarBytes::FULL_BYTES }>().prop_map(|mut bytes| {
+        // Big-endian, clearing top 2 bits ensures value fits into the scalar field
+        bytes[0] &= 0b00111111;
+        ScalarBytes::from(bytes)
+    })
+}
+
+/// Strategy for [`HistorySize`]
+pub fn arb_history_size() -> impl Strategy<Value = HistorySize> {
+    (1..=u64::MAX).prop_map(|history_size| {
+        HistorySize::new(NonZeroU64::new(history_size).expect("Not zero; qed"))
+    })
+}
+
+/// Strategy for [`LastArchivedBlock`]
+pub fn arb_last_archived_block() -> impl Strategy<Value = LastArchivedBlock> {
+    let archived_progress = prop_oneof![
+        Just(ArchivedBlockProgress::Complete),
+        any::<u32>().prop_map(ArchivedBlockProgress::Partial),
+    ];
+
+    (any::<u32>(), archived_progress).prop_map(|(number, archived_progress)| LastArchivedBlock {
+        number,
+        archived_progress,
+    })
+}
+
+/// Strategy for [`SegmentHeader`] of all versions
+pub fn arb_segment_header() -> impl Strategy<Value = SegmentHeader> {
+    (
+        any::<u64>(),
+        arb_bytes::<{ SegmentCommitment::SIZE }>(),
+        arb_bytes::<{ Blake3Hash::SIZE }>(),
+        arb_last_archived_block(),
+        proptest::option::of(any::<u32>()),
+    )
+        .prop_map(
+            |(
+                segment_index,
+                segment_commitment,
+                prev_segment_header_hash,
+                last_archived_block,
+                maybe_record_count,
+            )| {
+                let segment_index = SegmentIndex::new(segment_index);
+                let segment_commitment = SegmentCommitment::from(segment_commitment);
+                let prev_segment_header_hash = Blake3Hash::from(prev_segment_header_hash);
+
+                match maybe_record_count {
+                    None => SegmentHeader::V0 {
+                        segment_index,
+                        segment_commitment,
+                        prev_segment_header_hash,
+                        last_archived_block,
+                    },
+                    Some(record_count) => SegmentHeader::V1 {
+                        segment_index,
+                        segment_commitment,
+                        prev_segment_header_hash,
+                        last_archived_block,
+                        record_count,
+                    },
+                }
+            },
+        )
+}
+
+/// Strategy for [`Solution`] with reward address produced by `reward_address` strategy
+pub fn arb_solution<RewardAddress>(
+    reward_address: impl Strategy<Value = RewardAddress>,
+) -> impl Strategy<Value = Solution<RewardAddress>>
+where
+    RewardAddress: fmt::Debug,
+{
+    (
+        (
+            arb_public_key(),
+            reward_address,
+            any::<u16>(),
+            arb_history_size(),
+            any::<u16>(),
+        ),
+        (
+            arb_bytes::<{ RecordCommitment::SIZE }>(),
+            arb_bytes::<{ RecordWitness::SIZE }>(),
+            arb_scalar_bytes(),
+            arb_bytes::<{ ChunkWitness::SIZE }>(),
+            arb_bytes::<{ PosProof::SIZE }>(),
+        ),
+    )
+        .prop_map(
+            |(
+                (public_key, reward_address, sector_index, history_size, piece_offset),
+                (record_commitment, record_witness, chunk, chunk_witness, proof_of_space),
+            )| Solution {
+                public_key,
+                reward_address,
+                sector_index,
+                history_size,
+                piece_offset: PieceOffset::from(piece_offset),
+                record_commitment: RecordCommitment::from(record_commitment),
+                record_witness: RecordWitness::from(record_witness),
+                chunk,
+                chunk_witness: ChunkWitness::from(chunk_witness),
+                proof_of_space: PosProof::from(proof_of_space),
+            },
+        )
+}
@@ -0,0 +1,29 @@
+use crate::testing::{arb_segment_header, arb_solution, arb_u256};
+use parity_scale_codec::{Decode, Encode};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn u256_encoding_round_trip(value in arb_u256()) {
+        prop_assert_eq!(crate::U256::from_le_bytes(value.to_le_bytes()), value);
+        prop_assert_eq!(crate::U256::decode(&mut value.encode().as_slice()).unwrap(), value);
+    }
+
+    #[test]
+    fn segment_header_encoding_round_trip(segment_header in arb_segment_header()) {
+        let encoded = segment_header.encode();
+        prop_assert_eq!(
+            crate::segments::SegmentHeader::decode(&mut encoded.as_slice()).unwrap(),
+            segment_header
+        );
+    }
+
+    #[test]
+    fn solution_encoding_round_trip(solution in arb_solution(any::<[u8; 32]>())) {
+        let encoded = solution.encode();
+        prop_assert_eq!(
+            crate::solutions::Solution::<[u8; 32]>::decode(&mut encoded.as_slice()).unwrap(),
+            solution
+        );
+    }
+}
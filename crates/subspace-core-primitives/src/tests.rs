@@ -1,12 +1,69 @@
-use crate::U256;
-use crate::pieces::PieceIndex;
-use crate::segments::{ArchivedHistorySegment, RecordedHistorySegment, SegmentIndex};
+use crate::hashes::{Blake3Hasher, blake3_hash, blake3_hash_list, blake3_hash_with_key};
+use crate::objects::{GlobalObject, GlobalObjectMapping, ObjectMappingIndex};
+use crate::pieces::{FlatPieces, Piece, PieceIndex, RawRecord};
+use crate::segments::{
+    ArchivedBlockProgress, ArchivedHistorySegment, LastArchivedBlock, ProgressOverflow,
+    RecordedHistorySegment, SegmentCommitment, SegmentHeader, SegmentHeaderChainError,
+    SegmentIndex, verify_chain,
+};
+use crate::solutions::bidirectional_distance;
+use crate::{RANDOMNESS_CONTEXT, Randomness, U256};
+use num_traits::{WrappingAdd, WrappingSub};
+use parity_scale_codec::{Decode, Encode};
 
 #[test]
 fn piece_distance_middle() {
     assert_eq!(U256::MIDDLE, U256::MAX / 2);
 }
 
+#[test]
+fn bidirectional_distance_u256() {
+    // Wraps around at the boundary
+    assert_eq!(
+        bidirectional_distance(&U256::zero(), &U256::MAX),
+        U256::one()
+    );
+    assert_eq!(
+        bidirectional_distance(&U256::MAX, &U256::zero()),
+        U256::one()
+    );
+    assert_eq!(
+        bidirectional_distance(&U256::one(), &U256::MAX),
+        U256::from(2u8)
+    );
+
+    // Distance to itself is zero
+    for value in [U256::zero(), U256::one(), U256::MIDDLE, U256::MAX] {
+        assert_eq!(bidirectional_distance(&value, &value), U256::zero());
+    }
+
+    // Symmetric across the middle
+    let below_middle = U256::MIDDLE.wrapping_sub(&U256::from(10u8));
+    let above_middle = U256::MIDDLE.wrapping_add(&U256::from(10u8));
+    assert_eq!(
+        bidirectional_distance(&below_middle, &above_middle),
+        U256::from(20u8)
+    );
+    assert_eq!(
+        bidirectional_distance(&above_middle, &below_middle),
+        U256::from(20u8)
+    );
+
+    // Opposite points of the ring, `MIDDLE` is the largest possible distance
+    assert_eq!(
+        bidirectional_distance(&U256::zero(), &U256::MIDDLE),
+        U256::MIDDLE
+    );
+    assert_eq!(
+        bidirectional_distance(&U256::zero(), &U256::MIDDLE.wrapping_add(&U256::one())),
+        U256::MIDDLE.wrapping_add(&U256::one())
+    );
+    assert_eq!(
+        bidirectional_distance(&U256::zero(), &U256::MIDDLE.wrapping_add(&U256::from(2u8))),
+        U256::MIDDLE
+    );
+}
+
 /// piece index, piece position, source position, segment index, next source index
 const SOURCE_PIECE_INDEX_TEST_CASES: &[(u64, u32, u32, u64, u64)] = &[
     (0, 0, 0, 0, 2),
@@ -156,3 +213,552 @@ fn parity_piece_index_next_source_panic() {
         piece_index.next_source_index();
     }
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn u256_serde_representations() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Hex(#[serde(with = "crate::u256::as_hex")] U256);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Decimal(#[serde(with = "crate::u256::as_decimal")] U256);
+
+    for value in [
+        U256::zero(),
+        U256::one(),
+        U256::from(u64::MAX),
+        U256::MIDDLE,
+        U256::MAX,
+    ] {
+        let hex = serde_json::to_string(&Hex(value)).unwrap();
+        assert_eq!(serde_json::from_str::<Hex>(&hex).unwrap(), Hex(value));
+
+        let decimal = serde_json::to_string(&Decimal(value)).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Decimal>(&decimal).unwrap(),
+            Decimal(value)
+        );
+    }
+
+    assert_eq!(
+        serde_json::to_string(&Hex(U256::one())).unwrap(),
+        "\"0000000000000000000000000000000000000000000000000000000000000001\""
+    );
+    assert_eq!(
+        serde_json::to_string(&Decimal(U256::from(1234u32))).unwrap(),
+        "\"1234\""
+    );
+    assert!(serde_json::from_str::<Decimal>("\"-1\"").is_err());
+    assert!(serde_json::from_str::<Hex>("\"01\"").is_err());
+}
+
+#[test]
+fn blake3_hasher_incremental() {
+    let data = (0..=u8::MAX).cycle().take(10_000).collect::<Vec<u8>>();
+    let key = [42; 32];
+
+    let mut hasher = Blake3Hasher::new();
+    let mut keyed_hasher = Blake3Hasher::new_keyed(&key);
+    for chunk in data.chunks(333) {
+        hasher.update(chunk);
+        keyed_hasher.update(chunk);
+    }
+
+    assert_eq!(hasher.finalize(), blake3_hash(&data));
+    assert_eq!(keyed_hasher.finalize(), blake3_hash_with_key(&key, &data));
+}
+
+fn segment_header_chain(length: u64) -> Vec<SegmentHeader> {
+    let mut headers = Vec::<SegmentHeader>::new();
+    for segment_index in 0..length {
+        headers.push(SegmentHeader::V0 {
+            segment_index: SegmentIndex::new(segment_index),
+            segment_commitment: SegmentCommitment::default(),
+            prev_segment_header_hash: headers.last().map(SegmentHeader::hash).unwrap_or_default(),
+            last_archived_block: LastArchivedBlock {
+                number: segment_index as u32,
+                archived_progress: ArchivedBlockProgress::Complete,
+            },
+        });
+    }
+    headers
+}
+
+#[test]
+fn segment_header_chain_verification() {
+    let headers = segment_header_chain(3);
+    assert_eq!(verify_chain(&headers), Ok(()));
+    assert_eq!(verify_chain(&headers[1..]), Ok(()));
+    assert_eq!(verify_chain(&[]), Ok(()));
+
+    // Broken hash link
+    {
+        let mut headers = headers.clone();
+        let SegmentHeader::V0 {
+            prev_segment_header_hash,
+            ..
+        } = &mut headers[2]
+        else {
+            unreachable!("Only V0 segment headers are created above");
+        };
+        prev_segment_header_hash[0] ^= 1;
+
+        assert_eq!(
+            verify_chain(&headers),
+            Err(SegmentHeaderChainError::HashMismatch { position: 2 })
+        );
+    }
+
+    // Skipped segment index
+    {
+        let headers = [headers[0], headers[2]];
+
+        assert_eq!(
+            verify_chain(&headers),
+            Err(SegmentHeaderChainError::IndexGap {
+                position: 1,
+                expected: SegmentIndex::ONE,
+                actual: SegmentIndex::new(2),
+            })
+        );
+    }
+}
+
+#[test]
+fn segment_header_last_archived_block_helpers() {
+    let mut segment_header = segment_header_chain(1)[0];
+    assert_eq!(segment_header.last_block_number(), 0);
+    assert!(segment_header.is_block_fully_archived());
+    assert_eq!(segment_header.partial_archived(), None);
+
+    let SegmentHeader::V0 {
+        last_archived_block,
+        ..
+    } = &mut segment_header
+    else {
+        unreachable!("Only V0 segment headers are created above");
+    };
+    *last_archived_block = LastArchivedBlock {
+        number: 10,
+        archived_progress: ArchivedBlockProgress::Partial(100),
+    };
+    assert_eq!(segment_header.last_block_number(), 10);
+    assert!(!segment_header.is_block_fully_archived());
+    assert_eq!(segment_header.partial_archived(), Some(100));
+}
+
+#[test]
+fn segment_header_versions_encoding() {
+    let segment_header_v0 = segment_header_chain(2)[1];
+    let SegmentHeader::V0 {
+        segment_index,
+        segment_commitment,
+        prev_segment_header_hash,
+        last_archived_block,
+    } = segment_header_v0
+    else {
+        unreachable!("Only V0 segment headers are created above");
+    };
+    let segment_header_v1 = SegmentHeader::V1 {
+        segment_index,
+        segment_commitment,
+        prev_segment_header_hash,
+        last_archived_block,
+        record_count: RecordedHistorySegment::NUM_RAW_RECORDS as u32,
+    };
+
+    let encoded_v0 = segment_header_v0.encode();
+    // Encoding of V0 is unchanged: variant index followed by fields
+    assert_eq!(encoded_v0[0], 0);
+    assert_eq!(
+        SegmentHeader::decode(&mut encoded_v0.as_slice()).unwrap(),
+        segment_header_v0
+    );
+
+    let encoded_v1 = segment_header_v1.encode();
+    assert_eq!(encoded_v1[0], 1);
+    assert_eq!(encoded_v1.len(), encoded_v0.len() + size_of::<u32>());
+    assert_eq!(
+        SegmentHeader::decode(&mut encoded_v1.as_slice()).unwrap(),
+        segment_header_v1
+    );
+
+    // Accessors work the same way across versions
+    assert_eq!(segment_header_v1.segment_index(), segment_index);
+    assert_eq!(
+        segment_header_v1.prev_segment_header_hash(),
+        prev_segment_header_hash
+    );
+    assert_eq!(segment_header_v0.record_count(), None);
+    assert_eq!(
+        segment_header_v1.record_count(),
+        Some(RecordedHistorySegment::NUM_RAW_RECORDS as u32)
+    );
+    assert_ne!(segment_header_v0.hash(), segment_header_v1.hash());
+}
+
+#[test]
+fn archived_block_progress_accumulation() {
+    let block_size = 1000;
+    let mut progress = ArchivedBlockProgress::default();
+    assert_eq!(progress.remaining(block_size), Some(0));
+
+    progress.add_partial(300).unwrap();
+    assert_eq!(progress, ArchivedBlockProgress::Partial(300));
+    assert_eq!(progress.remaining(block_size), Some(700));
+
+    progress.add_partial(700).unwrap();
+    assert_eq!(progress, ArchivedBlockProgress::Partial(1000));
+    assert_eq!(progress.remaining(block_size), Some(0));
+    assert_eq!(progress.remaining(block_size - 1), None);
+
+    // Caller decides when the block is complete
+    progress = ArchivedBlockProgress::Complete;
+    assert_eq!(progress.partial(), None);
+
+    let mut progress = ArchivedBlockProgress::Partial(u32::MAX - 1);
+    assert_eq!(
+        progress.add_partial(2),
+        Err(ProgressOverflow {
+            partial: u32::MAX - 1,
+            bytes: 2
+        })
+    );
+    // Unchanged on overflow
+    assert_eq!(progress, ArchivedBlockProgress::Partial(u32::MAX - 1));
+    progress.add_partial(1).unwrap();
+    assert_eq!(progress, ArchivedBlockProgress::Partial(u32::MAX));
+}
+
+#[test]
+fn segment_piece_indexes_mapping() {
+    for segment_index in [
+        SegmentIndex::ZERO,
+        SegmentIndex::ONE,
+        SegmentIndex::new(u64::MAX / ArchivedHistorySegment::NUM_PIECES as u64 - 1),
+    ] {
+        let piece_indexes = segment_index.piece_indexes().collect::<Vec<_>>();
+        assert_eq!(piece_indexes.len(), ArchivedHistorySegment::NUM_PIECES);
+        assert_eq!(piece_indexes, segment_index.segment_piece_indexes());
+        assert_eq!(piece_indexes[0], segment_index.first_piece_index());
+        assert_eq!(
+            piece_indexes[ArchivedHistorySegment::NUM_PIECES - 1],
+            segment_index.last_piece_index()
+        );
+
+        for piece_index in piece_indexes {
+            assert_eq!(piece_index.segment_index(), segment_index);
+        }
+
+        // Last piece of one segment and first piece of the next one
+        let next_segment_index = segment_index + SegmentIndex::ONE;
+        assert_eq!(
+            segment_index.last_piece_index() + PieceIndex::ONE,
+            next_segment_index.first_piece_index()
+        );
+        assert_eq!(
+            next_segment_index.first_piece_index().segment_index(),
+            next_segment_index
+        );
+    }
+}
+
+#[test]
+fn piece_checksum() {
+    let mut piece = Piece::default();
+    piece
+        .iter_mut()
+        .enumerate()
+        .for_each(|(index, byte)| *byte = index as u8);
+    let checksum = piece.checksum();
+    assert!(piece.verify_checksum(&checksum));
+
+    piece[Piece::SIZE / 2] ^= 1;
+    assert_ne!(piece.checksum(), checksum);
+    assert!(!piece.verify_checksum(&checksum));
+}
+
+#[cfg(feature = "text-encoding")]
+#[test]
+fn piece_base64() {
+    use crate::pieces::PieceDecodingError;
+    use rand::prelude::*;
+
+    let mut piece = Piece::default();
+    StdRng::seed_from_u64(0).fill_bytes(piece.as_mut());
+
+    assert_eq!(Piece::from_base64(&piece.to_base64()).unwrap(), piece);
+
+    // Wrong length
+    assert_eq!(
+        Piece::from_base64(&piece.to_base64()[..64]),
+        Err(PieceDecodingError::InvalidLength {
+            expected: Piece::SIZE,
+            actual: 48,
+        })
+    );
+
+    // Bad characters
+    assert!(matches!(
+        Piece::from_base64("not base64!"),
+        Err(PieceDecodingError::InvalidBase64(_))
+    ));
+}
+
+#[test]
+fn piece_zeroed() {
+    // Must be usable in const context
+    static ZEROED: Piece = Piece::zeroed();
+
+    let piece = ZEROED.clone();
+    assert_eq!(piece.len(), Piece::SIZE);
+    assert!(piece.iter().all(|&byte| byte == 0));
+    assert_eq!(piece, Piece::default());
+
+    // Mutation doesn't affect other zeroed pieces
+    let mut piece = Piece::zeroed();
+    piece.as_mut()[0] = 1;
+    assert!(Piece::zeroed().iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn piece_safe_bytes() {
+    use crate::pieces::SafeBytesError;
+    use rand::prelude::*;
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    // Source piece has zero padding in every record chunk
+    let mut piece = Piece::default();
+    rng.fill_bytes(piece.as_mut());
+    piece.record_mut().iter_mut().for_each(|chunk| chunk[0] = 0);
+
+    let safe_bytes = piece.to_safe_bytes().unwrap();
+    assert_eq!(safe_bytes.len(), Piece::SAFE_SIZE);
+    assert_eq!(Piece::from_safe_bytes(&safe_bytes).unwrap(), piece);
+
+    assert_eq!(
+        Piece::from_safe_bytes(&safe_bytes[1..]),
+        Err(SafeBytesError::InvalidLength {
+            expected: Piece::SAFE_SIZE,
+            actual: Piece::SAFE_SIZE - 1,
+        })
+    );
+
+    // Non-zero padding can't be removed without losing data
+    piece.record_mut()[3][0] = 1;
+    assert_eq!(
+        piece.to_safe_bytes(),
+        Err(SafeBytesError::NonZeroPadding { chunk: 3 })
+    );
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn flat_pieces_from_mmap() {
+    use std::io::Write;
+
+    let pieces = (0..4u8)
+        .map(|index| {
+            let mut piece = Piece::default();
+            piece.as_mut().fill(index);
+            piece
+        })
+        .collect::<Vec<_>>();
+
+    let mut file = tempfile::tempfile().unwrap();
+    for piece in &pieces {
+        file.write_all(piece.as_ref()).unwrap();
+    }
+
+    // SAFETY: Temporary file is not modified while mapped
+    let flat_pieces = unsafe { FlatPieces::from_mmap(&file) }.unwrap();
+    assert_eq!(flat_pieces.len(), pieces.len());
+    assert_eq!(flat_pieces.pieces().collect::<Vec<_>>(), pieces);
+    assert_eq!(
+        flat_pieces.source_pieces().collect::<Vec<_>>(),
+        vec![pieces[0].clone(), pieces[2].clone()]
+    );
+
+    // File length must be a multiple of piece size
+    file.write_all(&[0; 1]).unwrap();
+    // SAFETY: Temporary file is not modified while mapped
+    assert!(unsafe { FlatPieces::from_mmap(&file) }.is_err());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn flat_pieces_par_pieces() {
+    use rayon::prelude::*;
+
+    let mut flat_pieces = FlatPieces::new(8);
+    flat_pieces
+        .iter_mut()
+        .enumerate()
+        .for_each(|(index, piece)| piece.fill(index as u8));
+
+    let sequential = flat_pieces.iter().collect::<Vec<_>>();
+    let parallel = flat_pieces.par_pieces().collect::<Vec<_>>();
+    assert_eq!(parallel.len(), flat_pieces.len());
+    assert_eq!(sequential, parallel);
+}
+
+#[test]
+fn flat_pieces_from_pieces() {
+    let pieces = (0..4u8)
+        .map(|index| {
+            let mut piece = Piece::default();
+            piece.fill(index);
+            piece
+        })
+        .collect::<Vec<_>>();
+
+    let flat_pieces = FlatPieces::from_pieces(pieces.clone().into_iter());
+    assert_eq!(flat_pieces.len(), pieces.len());
+    assert_eq!(flat_pieces.pieces().collect::<Vec<_>>(), pieces);
+
+    assert_eq!(FlatPieces::try_from(pieces.clone()), Ok(flat_pieces));
+    assert_eq!(FlatPieces::try_from(Vec::<Piece>::new()), Err(()));
+}
+
+#[test]
+fn object_mapping_index() {
+    let object_a = GlobalObject {
+        hash: blake3_hash(b"a"),
+        piece_index: PieceIndex::new(0),
+        offset: 0,
+    };
+    // Starts close to the end of the piece and continues in the next source piece
+    let object_b = GlobalObject {
+        hash: blake3_hash(b"b"),
+        piece_index: PieceIndex::new(2),
+        offset: RawRecord::SIZE as u32 - 10,
+    };
+    // The same object stored again later in the history
+    let object_b_again = GlobalObject {
+        hash: object_b.hash,
+        piece_index: PieceIndex::new(6),
+        offset: 100,
+    };
+
+    let mut index = ObjectMappingIndex::from_mapping(&GlobalObjectMapping::from_objects([
+        object_b_again,
+        object_a,
+    ]));
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.get(&object_a.hash), Some(&object_a));
+    assert_eq!(index.get(&object_b.hash), Some(&object_b_again));
+
+    index.insert(object_b);
+    // Duplicate is ignored
+    index.insert(object_b);
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.get(&object_b.hash), Some(&object_b));
+    assert_eq!(index.get_all(&object_b.hash), &[object_b, object_b_again]);
+
+    assert_eq!(index.get(&blake3_hash(b"c")), None);
+    assert!(index.get_all(&blake3_hash(b"c")).is_empty());
+}
+
+#[test]
+fn segment_index_parsing_and_arithmetic() {
+    for segment_index in [
+        SegmentIndex::ZERO,
+        SegmentIndex::new(123),
+        SegmentIndex::new(u64::MAX),
+    ] {
+        assert_eq!(
+            segment_index.to_string().parse::<SegmentIndex>(),
+            Ok(segment_index)
+        );
+    }
+    assert_eq!(SegmentIndex::new(42).to_string(), "42");
+    assert!("-1".parse::<SegmentIndex>().is_err());
+    assert!("18446744073709551616".parse::<SegmentIndex>().is_err());
+    assert!("".parse::<SegmentIndex>().is_err());
+    assert!("0x10".parse::<SegmentIndex>().is_err());
+
+    assert_eq!(SegmentIndex::ZERO.next(), Some(SegmentIndex::ONE));
+    assert_eq!(SegmentIndex::new(u64::MAX).next(), None);
+    assert_eq!(
+        SegmentIndex::new(u64::MAX - 1).checked_add(SegmentIndex::ONE),
+        Some(SegmentIndex::new(u64::MAX))
+    );
+    assert_eq!(
+        SegmentIndex::new(u64::MAX).checked_add(SegmentIndex::ONE),
+        None
+    );
+    assert_eq!(
+        SegmentIndex::ZERO.saturating_sub(SegmentIndex::ONE),
+        SegmentIndex::ZERO
+    );
+
+    assert_eq!(
+        SegmentIndex::NUM_PIECES,
+        ArchivedHistorySegment::NUM_PIECES as u64
+    );
+    assert_eq!(
+        SegmentIndex::ONE.first_piece_index(),
+        PieceIndex::new(SegmentIndex::NUM_PIECES)
+    );
+}
+
+#[test]
+fn randomness_derivation() {
+    assert_eq!(Randomness::derive(b"input"), Randomness::derive(b"input"));
+    assert_ne!(Randomness::derive(b"input"), Randomness::derive(b"other"));
+    assert_ne!(Randomness::derive(b""), Randomness::derive(b"\0"));
+    // Domain separated from plain hash of the input
+    assert_ne!(
+        Randomness::derive(b"input"),
+        Randomness::from(*blake3_hash(b"input"))
+    );
+    // Keyed with the context rather than prefixed with it
+    assert_ne!(
+        Randomness::derive(b"input"),
+        Randomness::from(*blake3_hash_list(&[RANDOMNESS_CONTEXT, b"input"]))
+    );
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_core_types() {
+    use crate::solutions::Solution;
+    use arbitrary::{Arbitrary, Unstructured};
+    use rand::prelude::*;
+
+    // Full range of `U256` is reachable
+    assert_eq!(
+        U256::arbitrary(&mut Unstructured::new(&[0xff; 32])).unwrap(),
+        U256::MAX
+    );
+    assert_eq!(
+        U256::arbitrary(&mut Unstructured::new(&[0; 32])).unwrap(),
+        U256::zero()
+    );
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let mut data = vec![0u8; 4096];
+        rng.fill_bytes(&mut data);
+        let mut u = Unstructured::new(&data);
+
+        U256::arbitrary(&mut u).unwrap();
+        PieceIndex::arbitrary(&mut u).unwrap();
+        SegmentIndex::arbitrary(&mut u).unwrap();
+
+        let segment_header = SegmentHeader::arbitrary(&mut u).unwrap();
+        assert_eq!(
+            SegmentHeader::decode(&mut segment_header.encode().as_slice()).unwrap(),
+            segment_header
+        );
+
+        let solution = Solution::<[u8; 32]>::arbitrary(&mut u).unwrap();
+        assert_eq!(
+            Solution::<[u8; 32]>::decode(&mut solution.encode().as_slice()).unwrap(),
+            solution
+        );
+    }
+}
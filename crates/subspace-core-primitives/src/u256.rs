@@ -0,0 +1,72 @@
+//! Serde helpers for [`U256`] that allow fields to opt into specific representation.
+//!
+//! Usage:
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use subspace_core_primitives::U256;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Example {
+//!     #[serde(with = "subspace_core_primitives::u256::as_hex")]
+//!     hex: U256,
+//!     #[serde(with = "subspace_core_primitives::u256::as_decimal")]
+//!     decimal: U256,
+//! }
+//! ```
+
+/// Serialize [`U256`] as hex string of big-endian bytes (without `0x` prefix).
+///
+/// [`U256`]: crate::U256
+pub mod as_hex {
+    use crate::U256;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct U256Hex(#[serde(with = "hex")] [u8; 32]);
+
+    /// Serialize [`U256`] as hex string.
+    pub fn serialize<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        U256Hex(value.to_be_bytes()).serialize(serializer)
+    }
+
+    /// Deserialize [`U256`] from hex string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(U256::from_be_bytes(U256Hex::deserialize(deserializer)?.0))
+    }
+}
+
+/// Serialize [`U256`] as decimal string.
+///
+/// [`U256`]: crate::U256
+pub mod as_decimal {
+    use crate::{U256, private_u256};
+    use scale_info::prelude::string::String;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serialize [`U256`] as decimal string.
+    pub fn serialize<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    /// Deserialize [`U256`] from decimal string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        private_u256::U256::from_dec_str(&s)
+            .map(U256)
+            .map_err(|error| D::Error::custom(format_args!("Invalid decimal U256: {error:?}")))
+    }
+}
@@ -6,6 +6,9 @@
 //! It is typically not needed to construct these data structures explicitly outside of this crate,
 //! instead they will be returned as a result of certain operations (like plotting).
 
+#[cfg(test)]
+mod tests;
+
 use bitvec::prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use rayon::prelude::*;
@@ -0,0 +1,65 @@
+use crate::sector::{SectorContentsMap, SectorContentsMapFromBytesError};
+use subspace_core_primitives::hashes::{Blake3Hash, blake3_hash};
+use subspace_core_primitives::pieces::Record;
+
+const PIECES_IN_SECTOR: u16 = 4;
+
+fn encoded_sector_contents_map() -> Vec<u8> {
+    let sector_contents_map = SectorContentsMap::new(PIECES_IN_SECTOR);
+    let mut bytes = vec![0; SectorContentsMap::encoded_size(PIECES_IN_SECTOR)];
+    sector_contents_map.encode_into(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn sector_contents_map_from_valid_bytes() {
+    let bytes = encoded_sector_contents_map();
+
+    let sector_contents_map = SectorContentsMap::from_bytes(&bytes, PIECES_IN_SECTOR).unwrap();
+    assert_eq!(
+        sector_contents_map.num_encoded_record_chunks().len(),
+        usize::from(PIECES_IN_SECTOR)
+    );
+}
+
+#[test]
+fn sector_contents_map_from_truncated_bytes() {
+    let bytes = encoded_sector_contents_map();
+
+    assert_eq!(
+        SectorContentsMap::from_bytes(&bytes[..bytes.len() - 1], PIECES_IN_SECTOR).unwrap_err(),
+        SectorContentsMapFromBytesError::InvalidBytesLength {
+            expected: bytes.len(),
+            actual: bytes.len() - 1,
+        }
+    );
+}
+
+#[test]
+fn sector_contents_map_from_corrupted_bytes() {
+    let mut bytes = encoded_sector_contents_map();
+    bytes[0] ^= 1;
+
+    assert_eq!(
+        SectorContentsMap::from_bytes(&bytes, PIECES_IN_SECTOR).unwrap_err(),
+        SectorContentsMapFromBytesError::ChecksumMismatch
+    );
+}
+
+#[test]
+fn sector_contents_map_from_too_many_encoded_chunks() {
+    let mut bytes = encoded_sector_contents_map();
+    // Mark all chunks of all records as encoded, which is more than record contains, and fix the
+    // checksum, so that only the contents are invalid
+    let (contents, checksum) = bytes.split_at_mut(bytes.len() - Blake3Hash::SIZE);
+    contents.fill(u8::MAX);
+    checksum.copy_from_slice(blake3_hash(contents).as_ref());
+
+    assert_eq!(
+        SectorContentsMap::from_bytes(&bytes, PIECES_IN_SECTOR).unwrap_err(),
+        SectorContentsMapFromBytesError::InvalidEncodedRecordChunks {
+            actual: Record::NUM_S_BUCKETS,
+            max: Record::NUM_CHUNKS,
+        }
+    );
+}
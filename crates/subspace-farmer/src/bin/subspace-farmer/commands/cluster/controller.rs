@@ -158,7 +158,7 @@ pub(super) async fn controller(
             Arc::downgrade(&plotted_pieces),
             node_client.clone(),
             FarmerCaches::from(Arc::clone(&farmer_caches)),
-            Some(registry),
+            Some(&mut *registry),
         )
         .map_err(|error| anyhow!("Failed to configure networking: {error}"))?
     };
@@ -202,6 +202,7 @@ pub(super) async fn controller(
                         ..ExponentialBackoff::default()
                     },
                 },
+                None,
             );
 
             let fut = farmer_cache_worker
@@ -232,6 +233,7 @@ pub(super) async fn controller(
                 ..ExponentialBackoff::default()
             },
         },
+        Some(registry),
     );
 
     let mut controller_services = (0..service_instances.get())
@@ -482,6 +482,7 @@ where
                 ..ExponentialBackoff::default()
             },
         },
+        Some(&mut registry),
     );
 
     let farmer_cache_worker_fut = run_future_in_dedicated_thread(
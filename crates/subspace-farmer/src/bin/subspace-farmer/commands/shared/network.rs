@@ -1,11 +1,13 @@
 use async_lock::RwLock as AsyncRwLock;
 use clap::Parser;
+use futures::StreamExt;
 use parking_lot::Mutex;
 use prometheus_client::registry::Registry;
 use std::collections::HashSet;
 use std::fmt;
 use std::hash::Hash;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::NonZeroU32;
 use std::path::Path;
 use std::sync::{Arc, Weak};
 use subspace_farmer::KNOWN_PEERS_CACHE_SIZE;
@@ -22,10 +24,14 @@ use subspace_networking::protocols::request_response::handlers::cached_piece_by_
 use subspace_networking::protocols::request_response::handlers::piece_by_index::{
     PieceByIndexRequest, PieceByIndexRequestHandler, PieceByIndexResponse,
 };
+use subspace_networking::protocols::request_response::handlers::piece_range::{
+    PieceRangeRequestHandler, PieceRangeResponse,
+};
 use subspace_networking::protocols::request_response::handlers::segment_header::{
     SegmentHeaderBySegmentIndexesRequestHandler, SegmentHeaderRequest, SegmentHeaderResponse,
 };
 use subspace_networking::utils::multihash::ToMultihash;
+use subspace_networking::utils::peer_rate_limiter::PeerRateLimiter;
 use subspace_networking::utils::strip_peer_id;
 use subspace_networking::{
     Config, KademliaMode, KnownPeersManager, KnownPeersManagerConfig, Node, NodeRunner, WeakNode,
@@ -76,6 +82,13 @@ pub(in super::super) struct NetworkArgs {
     /// Known external addresses.
     #[arg(long = "external-address")]
     pub(in super::super) external_addresses: Vec<Multiaddr>,
+    /// Rate at which piece requests from a single peer are served, per second.
+    #[arg(long, default_value_t = NonZeroU32::new(100).expect("Not zero; qed"))]
+    pub(in super::super) piece_requests_per_peer_rate: NonZeroU32,
+    /// Number of piece requests from a single peer that can be served in a burst before rate
+    /// limiting kicks in.
+    #[arg(long, default_value_t = NonZeroU32::new(1000).expect("Not zero; qed"))]
+    pub(in super::super) piece_requests_per_peer_burst: NonZeroU32,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -93,6 +106,8 @@ pub(in super::super) fn configure_network<FarmIndex, NC>(
         pending_in_connections,
         pending_out_connections,
         external_addresses,
+        piece_requests_per_peer_rate,
+        piece_requests_per_peer_burst,
     }: NetworkArgs,
     weak_plotted_pieces: Weak<AsyncRwLock<PlottedPieces<FarmIndex>>>,
     node_client: NC,
@@ -116,6 +131,11 @@ where
     .map(Box::new)?;
 
     let maybe_weak_node = Arc::new(Mutex::new(None::<WeakNode>));
+    let piece_request_rate_limiter = Arc::new(PeerRateLimiter::new(
+        piece_requests_per_peer_rate,
+        piece_requests_per_peer_burst,
+        in_connections.saturating_add(out_connections),
+    ));
     let default_config = Config::new(protocol_prefix, keypair, prometheus_metrics_registry);
     let config = Config {
         reserved_peers,
@@ -177,18 +197,77 @@ where
                     .in_current_span()
                 })
             },
-            PieceByIndexRequestHandler::create(move |_, request| {
+            {
+                let piece_request_rate_limiter = Arc::clone(&piece_request_rate_limiter);
+                let farmer_caches = farmer_caches.clone();
+
+                PieceRangeRequestHandler::create(move |peer_id, request| {
+                    let rate_limited = !piece_request_rate_limiter.try_acquire(peer_id);
+
+                    let farmer_caches = farmer_caches.clone();
+
+                    async move {
+                        if rate_limited {
+                            debug!(%peer_id, ?request, "Piece range request rate limit exceeded");
+
+                            return Some(PieceRangeResponse {
+                                pieces: Vec::new(),
+                                missing: request.piece_indexes().collect(),
+                            });
+                        }
+
+                        debug!(?request, "Piece range request received");
+
+                        // Only pieces from cache are served, reading many pieces from archival
+                        // storage at once is too expensive
+                        let mut response = PieceRangeResponse {
+                            pieces: Vec::new(),
+                            missing: Vec::new(),
+                        };
+                        let mut pieces_stream =
+                            farmer_caches.get_pieces(request.piece_indexes()).await;
+                        while let Some((piece_index, maybe_piece)) = pieces_stream.next().await {
+                            match maybe_piece {
+                                Some(piece) => {
+                                    response.pieces.push((piece_index, piece));
+                                }
+                                None => {
+                                    response.missing.push(piece_index);
+                                }
+                            }
+                        }
+                        response
+                            .pieces
+                            .sort_unstable_by_key(|(piece_index, _piece)| *piece_index);
+                        response.missing.sort_unstable();
+
+                        Some(response)
+                    }
+                    .in_current_span()
+                })
+            },
+            PieceByIndexRequestHandler::create(move |peer_id, request| {
                 let PieceByIndexRequest {
                     piece_index,
                     cached_pieces,
                 } = request;
-                debug!(?piece_index, "Piece request received. Trying cache...");
+                let rate_limited = !piece_request_rate_limiter.try_acquire(peer_id);
 
                 let weak_plotted_pieces = weak_plotted_pieces.clone();
                 let farmer_caches = farmer_caches.clone();
                 let mut cached_pieces = Arc::unwrap_or_clone(cached_pieces);
 
                 async move {
+                    if rate_limited {
+                        debug!(%peer_id, ?piece_index, "Piece request rate limit exceeded");
+
+                        return Some(PieceByIndexResponse {
+                            piece: None,
+                            cached_pieces: Vec::new(),
+                        });
+                    }
+
+                    debug!(?piece_index, "Piece request received. Trying cache...");
                     let piece_from_cache =
                         farmer_caches.get_piece(piece_index.to_multihash()).await;
                     cached_pieces.truncate(PieceByIndexRequest::RECOMMENDED_LIMIT);
@@ -2,6 +2,7 @@
 
 use crate::farm::plotted_pieces::PlottedPieces;
 use crate::farmer_cache::FarmerCaches;
+use crate::farmer_piece_getter::metrics::{FarmerPieceGetterMetrics, PieceSource};
 use crate::node_client::NodeClient;
 use async_lock::RwLock as AsyncRwLock;
 use async_trait::async_trait;
@@ -12,19 +13,24 @@ use futures::channel::mpsc;
 use futures::future::FusedFuture;
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt, Stream, StreamExt, stream};
+use prometheus_client::registry::Registry;
 use std::fmt;
 use std::hash::Hash;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Weak};
 use std::task::{Context, Poll};
+use std::time::Instant;
 use subspace_core_primitives::pieces::{Piece, PieceIndex};
 use subspace_data_retrieval::piece_getter::PieceGetter;
 use subspace_networking::utils::multihash::ToMultihash;
 use subspace_networking::utils::piece_provider::{PieceProvider, PieceValidator};
 use tracing::{debug, error, trace};
 
+mod metrics;
 pub mod piece_validator;
+#[cfg(test)]
+mod tests;
 
 const MAX_RANDOM_WALK_ROUNDS: usize = 15;
 
@@ -43,6 +49,7 @@ struct Inner<FarmIndex, PV, NC> {
     node_client: NC,
     plotted_pieces: Arc<AsyncRwLock<PlottedPieces<FarmIndex>>>,
     dsn_cache_retry_policy: DsnCacheRetryPolicy,
+    metrics: Option<FarmerPieceGetterMetrics>,
 }
 
 /// Farmer-specific piece getter.
@@ -82,6 +89,7 @@ where
         node_client: NC,
         plotted_pieces: Arc<AsyncRwLock<PlottedPieces<FarmIndex>>>,
         dsn_cache_retry_policy: DsnCacheRetryPolicy,
+        registry: Option<&mut Registry>,
     ) -> Self {
         Self {
             inner: Arc::new(Inner {
@@ -90,16 +98,23 @@ where
                 node_client,
                 plotted_pieces,
                 dsn_cache_retry_policy,
+                metrics: registry.map(FarmerPieceGetterMetrics::new),
             }),
         }
     }
 
     /// Fast way to get piece using various caches
     pub async fn get_piece_fast(&self, piece_index: PieceIndex) -> Option<Piece> {
-        self.get_piece_fast_internal(piece_index).await
+        let start = Instant::now();
+        let (piece, source) = self.get_piece_fast_internal(piece_index).await?;
+        self.observe_piece_retrieval_time(start, source);
+        Some(piece)
     }
 
-    async fn get_piece_fast_internal(&self, piece_index: PieceIndex) -> Option<Piece> {
+    async fn get_piece_fast_internal(
+        &self,
+        piece_index: PieceIndex,
+    ) -> Option<(Piece, PieceSource)> {
         let inner = &self.inner;
 
         trace!(%piece_index, "Getting piece from farmer cache");
@@ -109,7 +124,7 @@ where
             .await
         {
             trace!(%piece_index, "Got piece from farmer cache successfully");
-            return Some(piece);
+            return Some((piece, PieceSource::Local));
         }
 
         // L2 piece acquisition
@@ -120,7 +135,7 @@ where
                 .maybe_store_additional_piece(piece_index, &piece)
                 .await;
             trace!(%piece_index, %added_to_cache, "Got piece from DSN L2 cache");
-            return Some(piece);
+            return Some((piece, PieceSource::Network));
         }
 
         // Try node's RPC before reaching to L1 (archival storage on DSN)
@@ -132,7 +147,7 @@ where
                     .maybe_store_additional_piece(piece_index, &piece)
                     .await;
                 trace!(%piece_index, %added_to_cache, "Got piece from node successfully");
-                return Some(piece);
+                return Some((piece, PieceSource::Network));
             }
             Ok(None) => {
                 // Nothing to do
@@ -151,11 +166,17 @@ where
 
     /// Slow way to get piece using archival storage
     pub async fn get_piece_slow(&self, piece_index: PieceIndex) -> Option<Piece> {
-        self.get_piece_slow_internal(piece_index).await
+        let start = Instant::now();
+        let (piece, source) = self.get_piece_slow_internal(piece_index).await?;
+        self.observe_piece_retrieval_time(start, source);
+        Some(piece)
     }
 
     /// Slow way to get piece using archival storage
-    async fn get_piece_slow_internal(&self, piece_index: PieceIndex) -> Option<Piece> {
+    async fn get_piece_slow_internal(
+        &self,
+        piece_index: PieceIndex,
+    ) -> Option<(Piece, PieceSource)> {
         let inner = &self.inner;
 
         trace!(%piece_index, "Getting piece from local plot");
@@ -172,7 +193,7 @@ where
                 .maybe_store_additional_piece(piece_index, &piece)
                 .await;
             trace!(%piece_index, %added_to_cache, "Got piece from local plot successfully");
-            return Some(piece);
+            return Some((piece, PieceSource::Local));
         }
 
         // L1 piece acquisition
@@ -189,12 +210,18 @@ where
                 .maybe_store_additional_piece(piece_index, &piece)
                 .await;
             trace!(%piece_index, %added_to_cache, "DSN L1 lookup succeeded");
-            return Some(piece);
+            return Some((piece, PieceSource::Network));
         }
 
         None
     }
 
+    fn observe_piece_retrieval_time(&self, start: Instant, source: PieceSource) {
+        if let Some(metrics) = &self.inner.metrics {
+            metrics.observe_piece_retrieval_time(&start.elapsed(), source);
+        }
+    }
+
     /// Downgrade to [`WeakFarmerPieceGetter`] in order to break reference cycles with internally
     /// used [`Arc`]
     pub fn downgrade(&self) -> WeakFarmerPieceGetter<FarmIndex, PV, NC> {
@@ -213,6 +240,8 @@ where
     NC: NodeClient,
 {
     async fn get_piece(&self, piece_index: PieceIndex) -> anyhow::Result<Option<Piece>> {
+        let start = Instant::now();
+
         {
             let retries = AtomicU32::new(0);
             let max_retries = u32::from(self.inner.dsn_cache_retry_policy.max_retries);
@@ -222,9 +251,9 @@ where
             let maybe_piece_fut = retry(backoff, || async {
                 let current_attempt = retries.fetch_add(1, Ordering::Relaxed);
 
-                if let Some(piece) = self.get_piece_fast_internal(piece_index).await {
+                if let Some(piece_and_source) = self.get_piece_fast_internal(piece_index).await {
                     trace!(%piece_index, current_attempt, "Got piece fast");
-                    return Ok(Some(piece));
+                    return Ok(Some(piece_and_source));
                 }
                 if current_attempt >= max_retries {
                     if max_retries > 0 {
@@ -243,13 +272,15 @@ where
                 Err(backoff::Error::transient("Couldn't get piece fast"))
             });
 
-            if let Ok(Some(piece)) = maybe_piece_fut.await {
+            if let Ok(Some((piece, source))) = maybe_piece_fut.await {
                 trace!(%piece_index, "Got piece from cache successfully");
+                self.observe_piece_retrieval_time(start, source);
                 return Ok(Some(piece));
             }
         };
 
-        if let Some(piece) = self.get_piece_slow_internal(piece_index).await {
+        if let Some((piece, source)) = self.get_piece_slow_internal(piece_index).await {
+            self.observe_piece_retrieval_time(start, source);
             return Ok(Some(piece));
         }
 
@@ -270,6 +301,7 @@ where
 
         let fut = async move {
             let tx = &tx;
+            let start = Instant::now();
 
             let piece_count = piece_indices.len();
             debug!(%piece_count, "Getting pieces from farmer cache");
@@ -282,6 +314,7 @@ where
                     pieces_not_found_in_farmer_cache.push(piece_index);
                     continue;
                 };
+                self.observe_piece_retrieval_time(start, PieceSource::Local);
                 tx.unbounded_send((piece_index, Ok(Some(piece))))
                     .expect("This future isn't polled after receiver is dropped; qed");
             }
@@ -314,6 +347,7 @@ where
                     .maybe_store_additional_piece(piece_index, &piece)
                     .await;
                 trace!(%piece_index, %added_to_cache, "Got piece from DSN cache successfully");
+                self.observe_piece_retrieval_time(start, PieceSource::Network);
                 tx.unbounded_send((piece_index, Ok(Some(piece))))
                     .expect("This future isn't polled after receiver is dropped; qed");
             }
@@ -337,6 +371,7 @@ where
                                 .maybe_store_additional_piece(piece_index, &piece)
                                 .await;
                             trace!(%piece_index, %added_to_cache, "Got piece from node successfully");
+                            self.observe_piece_retrieval_time(start, PieceSource::Network);
 
                             tx.unbounded_send((piece_index, Ok(Some(piece))))
                                 .expect("This future isn't polled after receiver is dropped; qed");
@@ -370,7 +405,13 @@ where
             pieces_not_found_on_node
                 .into_iter()
                 .map(|piece_index| async move {
-                    let maybe_piece = self.get_piece_slow_internal(piece_index).await;
+                    let maybe_piece =
+                        self.get_piece_slow_internal(piece_index)
+                            .await
+                            .map(|(piece, source)| {
+                                self.observe_piece_retrieval_time(start, source);
+                                piece
+                            });
 
                     tx.unbounded_send((piece_index, Ok(maybe_piece)))
                         .expect("This future isn't polled after receiver is dropped; qed");
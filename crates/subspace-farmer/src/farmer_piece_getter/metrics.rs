@@ -0,0 +1,59 @@
+//! Metrics for farmer piece getter
+
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::histogram::{Histogram, exponential_buckets};
+use prometheus_client::registry::{Registry, Unit};
+use std::fmt;
+use std::time::Duration;
+
+/// Where piece was retrieved from
+#[derive(Debug, Copy, Clone)]
+pub(super) enum PieceSource {
+    /// Farmer cache or local plot
+    Local,
+    /// DSN or node
+    Network,
+}
+
+impl fmt::Display for PieceSource {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Local => "local",
+            Self::Network => "network",
+        })
+    }
+}
+
+/// Metrics for farmer piece getter
+#[derive(Debug)]
+pub(super) struct FarmerPieceGetterMetrics {
+    piece_retrieval_time: Family<Vec<(&'static str, String)>, Histogram>,
+}
+
+impl FarmerPieceGetterMetrics {
+    /// Create new instance
+    pub(super) fn new(registry: &mut Registry) -> Self {
+        let registry = registry.sub_registry_with_prefix("farmer_piece_getter");
+
+        let piece_retrieval_time = Family::<_, _>::new_with_constructor(|| {
+            Histogram::new(exponential_buckets(0.0002, 2.0, 18))
+        });
+        registry.register_with_unit(
+            "piece_retrieval_time",
+            "End-to-end piece retrieval time",
+            Unit::Seconds,
+            piece_retrieval_time.clone(),
+        );
+
+        Self {
+            piece_retrieval_time,
+        }
+    }
+
+    pub(super) fn observe_piece_retrieval_time(&self, time: &Duration, source: PieceSource) {
+        self.piece_retrieval_time
+            .get_or_create(&vec![("source", source.to_string())])
+            .observe(time.as_secs_f64());
+    }
+}
@@ -0,0 +1,29 @@
+use crate::farmer_piece_getter::metrics::{FarmerPieceGetterMetrics, PieceSource};
+use prometheus_client::encoding::text::encode;
+use prometheus_client::registry::Registry;
+use std::time::Duration;
+
+#[test]
+fn piece_retrieval_time_is_recorded_per_source() {
+    let mut registry = Registry::default();
+    let metrics = FarmerPieceGetterMetrics::new(&mut registry);
+
+    metrics.observe_piece_retrieval_time(&Duration::from_millis(1), PieceSource::Local);
+    metrics.observe_piece_retrieval_time(&Duration::from_millis(2), PieceSource::Network);
+    metrics.observe_piece_retrieval_time(&Duration::from_millis(3), PieceSource::Network);
+
+    let mut output = String::new();
+    encode(&mut output, &registry).unwrap();
+
+    assert!(
+        output
+            .contains("farmer_piece_getter_piece_retrieval_time_seconds_count{source=\"local\"} 1"),
+        "{output}"
+    );
+    assert!(
+        output.contains(
+            "farmer_piece_getter_piece_retrieval_time_seconds_count{source=\"network\"} 2"
+        ),
+        "{output}"
+    );
+}
@@ -158,6 +158,8 @@ impl DomainInstanceStarter {
                     >,
                     challenge_period: domain_block_pruning_depth,
                     domain_backend,
+                    telemetry_buffer_size: None,
+                    transaction_pool_limits: None,
                 };
 
                 let mut domain_node = domain_service::new_full::<
@@ -219,6 +221,8 @@ impl DomainInstanceStarter {
                     >,
                     challenge_period: domain_block_pruning_depth,
                     domain_backend,
+                    telemetry_buffer_size: None,
+                    transaction_pool_limits: None,
                 };
 
                 let mut domain_node = domain_service::new_full::<
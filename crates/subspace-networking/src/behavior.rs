@@ -27,6 +27,7 @@ use libp2p::ping::{Behaviour as Ping, Event as PingEvent};
 use libp2p::swarm::NetworkBehaviour;
 use libp2p::swarm::behaviour::toggle::Toggle;
 use std::convert::Infallible;
+use std::time::Duration;
 use void::Void as VoidEvent;
 
 type BlockListBehaviour = AllowBlockListBehaviour<BlockedPeers>;
@@ -42,6 +43,8 @@ pub(crate) struct BehaviorConfig {
     pub(crate) gossipsub: Option<GossipsubConfig>,
     /// The configuration for the [`RequestResponsesBehaviour`] protocol.
     pub(crate) request_response_protocols: Vec<Box<dyn RequestHandler>>,
+    /// Duration after which outgoing requests are considered timed out.
+    pub(crate) request_timeout: Duration,
     /// The upper bound for the number of concurrent inbound + outbound streams for request/response
     /// protocols.
     pub(crate) request_response_max_concurrent_streams: usize,
@@ -94,6 +97,7 @@ impl Behavior {
             request_response: RequestResponseFactoryBehaviour::new(
                 config.request_response_protocols,
                 config.request_response_max_concurrent_streams,
+                config.request_timeout,
             )
             //TODO: Convert to an error.
             .expect("RequestResponse protocols registration failed."),
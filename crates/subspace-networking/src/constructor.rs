@@ -11,8 +11,9 @@ use crate::protocols::autonat_wrapper::Config as AutonatWrapperConfig;
 use crate::protocols::request_response::request_response_factory::RequestHandler;
 use crate::protocols::reserved_peers::Config as ReservedPeersConfig;
 use crate::shared::Shared;
+use crate::utils::peer_reputation::PeerReputations;
 use crate::utils::rate_limiter::RateLimiter;
-use crate::utils::{SubspaceMetrics, strip_peer_id};
+use crate::utils::{BandwidthCounters, SubspaceMetrics, strip_peer_id};
 use backoff::{ExponentialBackoff, SystemClock};
 use futures::channel::mpsc;
 use libp2p::autonat::Config as AutonatConfig;
@@ -63,6 +64,8 @@ const SWARM_MAX_PENDING_OUTGOING_CONNECTIONS: u32 = 80;
 const KADEMLIA_QUERY_TIMEOUT: Duration = Duration::from_secs(40);
 const SWARM_MAX_ESTABLISHED_CONNECTIONS_PER_PEER: u32 = 3;
 const MAX_CONCURRENT_STREAMS_PER_CONNECTION: usize = 10;
+/// Default duration after which outgoing request-response requests are considered timed out.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
 // TODO: Consider moving this constant to configuration or removing `Toggle` wrapper when we find a
 //  use-case for gossipsub protocol.
 const ENABLE_GOSSIP_PROTOCOL: bool = false;
@@ -72,6 +75,9 @@ const TEMPORARY_BANS_DEFAULT_BACKOFF_INITIAL_INTERVAL: Duration = Duration::from
 const TEMPORARY_BANS_DEFAULT_BACKOFF_RANDOMIZATION_FACTOR: f64 = 0.1;
 const TEMPORARY_BANS_DEFAULT_BACKOFF_MULTIPLIER: f64 = 1.5;
 const TEMPORARY_BANS_DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(30 * 60);
+const PEER_REPUTATIONS_CACHE_SIZE: u32 = 10_000;
+const DEFAULT_PEER_BAN_REPUTATION_THRESHOLD: i32 = -500;
+const DEFAULT_PEER_BAN_DURATION: Duration = Duration::from_secs(3600);
 
 /// We pause between reserved peers dialing otherwise we could do multiple dials to offline peers
 /// wasting resources and producing a ton of log records.
@@ -163,6 +169,9 @@ impl RecordStore for DummyRecordStore {
 }
 
 /// [`Node`] configuration.
+///
+/// Node communicates over TCP with Noise authentication and Yamux multiplexing, `/dns`, `/dns4` and
+/// `/dns6` addresses are resolved at dial time.
 pub struct Config {
     /// Identity keypair of a node used for authenticated connections.
     pub keypair: identity::Keypair,
@@ -189,6 +198,9 @@ pub struct Config {
     pub known_peers_registry: Box<dyn KnownPeersRegistry>,
     /// The configuration for the `RequestResponsesBehaviour` protocol.
     pub request_response_protocols: Vec<Box<dyn RequestHandler>>,
+    /// Duration after which outgoing requests of all request-response protocols are considered
+    /// timed out.
+    pub request_timeout: Duration,
     /// Defines set of peers with a permanent connection (and reconnection if necessary).
     pub reserved_peers: Vec<Multiaddr>,
     /// Established incoming swarm connection limit.
@@ -203,13 +215,20 @@ pub struct Config {
     pub temporary_bans_cache_size: u32,
     /// Backoff policy for temporary banning of unreachable peers.
     pub temporary_ban_backoff: ExponentialBackoff,
+    /// Peers whose reputation drops to or below this threshold are banned.
+    pub peer_ban_reputation_threshold: i32,
+    /// For how long peers with low reputation are banned.
+    pub peer_ban_duration: Duration,
     /// Optional libp2p prometheus metrics. None will disable metrics gathering.
     pub libp2p_metrics: Option<Metrics>,
     /// Internal prometheus metrics. None will disable metrics gathering.
     pub metrics: Option<SubspaceMetrics>,
     /// Defines protocol version for the network peers. Affects network partition.
     pub protocol_version: String,
-    /// Addresses to bootstrap Kademlia network
+    /// Addresses to bootstrap Kademlia network, must end with `/p2p/<peer_id>`.
+    ///
+    /// DNS addresses like `/dns4/bootstrap.example.com/tcp/30533/p2p/<peer_id>` are supported and
+    /// resolved on every dial attempt, so changes in DNS records are picked up on reconnection.
     pub bootstrap_addresses: Vec<Multiaddr>,
     /// Kademlia mode. The default value is set to Static(Client). The peer won't add its address
     /// to other peers` Kademlia routing table. Changing this behaviour implies that a peer can
@@ -316,6 +335,7 @@ impl Config {
             initial_random_query_interval: Duration::from_secs(1),
             known_peers_registry: StubNetworkingParametersManager.boxed(),
             request_response_protocols: Vec::new(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
             yamux_config,
             reserved_peers: Vec::new(),
             max_established_incoming_connections: SWARM_MAX_ESTABLISHED_INCOMING_CONNECTIONS,
@@ -324,6 +344,8 @@ impl Config {
             max_pending_outgoing_connections: SWARM_MAX_PENDING_OUTGOING_CONNECTIONS,
             temporary_bans_cache_size: TEMPORARY_BANS_CACHE_SIZE,
             temporary_ban_backoff,
+            peer_ban_reputation_threshold: DEFAULT_PEER_BAN_REPUTATION_THRESHOLD,
+            peer_ban_duration: DEFAULT_PEER_BAN_DURATION,
             libp2p_metrics,
             metrics,
             protocol_version,
@@ -374,6 +396,7 @@ pub fn construct(config: Config) -> Result<(Node, NodeRunner), CreationError> {
         initial_random_query_interval,
         known_peers_registry,
         request_response_protocols,
+        request_timeout,
         reserved_peers,
         max_established_incoming_connections,
         max_established_outgoing_connections,
@@ -381,6 +404,8 @@ pub fn construct(config: Config) -> Result<(Node, NodeRunner), CreationError> {
         max_pending_outgoing_connections,
         temporary_bans_cache_size,
         temporary_ban_backoff,
+        peer_ban_reputation_threshold,
+        peer_ban_duration,
         libp2p_metrics,
         metrics,
         protocol_version,
@@ -425,6 +450,7 @@ pub fn construct(config: Config) -> Result<(Node, NodeRunner), CreationError> {
         kademlia,
         gossipsub,
         request_response_protocols,
+        request_timeout,
         request_response_max_concurrent_streams: {
             let max_num_connections = max_established_incoming_connections as usize
                 + max_established_outgoing_connections as usize;
@@ -460,6 +486,11 @@ pub fn construct(config: Config) -> Result<(Node, NodeRunner), CreationError> {
         }
     };
 
+    let bandwidth_counters = metrics
+        .as_ref()
+        .map(SubspaceMetrics::bandwidth_counters)
+        .unwrap_or_default();
+
     let temporary_bans = Arc::new(Mutex::new(TemporaryBans::new(
         temporary_bans_cache_size,
         temporary_ban_backoff,
@@ -472,6 +503,7 @@ pub fn construct(config: Config) -> Result<(Node, NodeRunner), CreationError> {
                 allow_non_global_addresses_in_dht,
                 keypair,
                 Arc::clone(&temporary_bans),
+                bandwidth_counters.clone(),
                 timeout,
                 yamux_config,
             )?)
@@ -519,7 +551,12 @@ pub fn construct(config: Config) -> Result<(Node, NodeRunner), CreationError> {
         max_pending_outgoing_connections,
     );
 
-    let shared = Arc::new(Shared::new(local_peer_id, command_sender, rate_limiter));
+    let shared = Arc::new(Shared::new(
+        local_peer_id,
+        command_sender,
+        rate_limiter,
+        bandwidth_counters,
+    ));
     let shared_weak = Arc::downgrade(&shared);
 
     let node = Node::new(shared);
@@ -533,6 +570,11 @@ pub fn construct(config: Config) -> Result<(Node, NodeRunner), CreationError> {
         known_peers_registry,
         reserved_peers: strip_peer_id(reserved_peers).into_iter().collect(),
         temporary_bans,
+        peer_reputations: PeerReputations::new(
+            peer_ban_reputation_threshold,
+            peer_ban_duration,
+            PEER_REPUTATIONS_CACHE_SIZE,
+        ),
         libp2p_metrics,
         metrics,
         protocol_version,
@@ -1,4 +1,6 @@
 use crate::constructor::temporary_bans::TemporaryBans;
+use crate::utils::BandwidthCounters;
+use futures::{AsyncRead, AsyncWrite};
 use libp2p::core::Transport;
 use libp2p::core::multiaddr::{Multiaddr, Protocol};
 use libp2p::core::muxing::StreamMuxerBox;
@@ -16,11 +18,14 @@ use std::task::{Context, Poll};
 use std::time::Duration;
 use tracing::debug;
 
-// Builds the transport stack that LibP2P will communicate over along with a relay client.
+// Builds the transport stack that LibP2P will communicate over: TCP authenticated with Noise and
+// multiplexed with Yamux, wrapped into DNS transport that resolves `/dns`, `/dns4` and `/dns6`
+// addresses on every dial attempt.
 pub(super) fn build_transport(
     allow_non_global_addresses_in_dht: bool,
     keypair: &identity::Keypair,
     temporary_bans: Arc<Mutex<TemporaryBans>>,
+    bandwidth_counters: BandwidthCounters,
     timeout: Duration,
     yamux_config: YamuxConfig,
 ) -> io::Result<Boxed<(PeerId, StreamMuxerBox)>> {
@@ -32,6 +37,10 @@ pub(super) fn build_transport(
             allow_non_global_addresses_in_dht,
             temporary_bans,
         )
+        .map(move |stream, _endpoint| MeteredStream {
+            inner: stream,
+            bandwidth_counters: bandwidth_counters.clone(),
+        })
     };
 
     let tcp_upgraded = {
@@ -109,7 +118,7 @@ where
                 return Err(TransportError::MultiaddrNotSupported(addr));
             }
             _ => {
-                // TODO: This will not catch DNS records pointing to private addresses
+                // DNS addresses are resolved by the outer DNS transport before reaching this point
             }
         }
 
@@ -135,3 +144,52 @@ where
         Pin::new(&mut self.base_transport).poll(cx)
     }
 }
+
+/// Stream wrapper that counts bytes read from and written to the inner stream
+#[derive(Debug)]
+struct MeteredStream<S> {
+    inner: S,
+    bandwidth_counters: BandwidthCounters,
+}
+
+impl<S> AsyncRead for MeteredStream<S>
+where
+    S: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(read)) = result {
+            self.bandwidth_counters.inbound.inc_by(read as u64);
+        }
+        result
+    }
+}
+
+impl<S> AsyncWrite for MeteredStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = result {
+            self.bandwidth_counters.outbound.inc_by(written as u64);
+        }
+        result
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
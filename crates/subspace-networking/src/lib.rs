@@ -18,12 +18,15 @@ pub use crate::behavior::persistent_parameters::{
     KnownPeersRegistry, PeerAddressRemovedEvent,
 };
 pub use crate::node::{
-    GetClosestPeersError, Node, SendRequestError, SubscribeError, TopicSubscription, WeakNode,
+    BannedPeersError, GetClosestPeersError, NODE_EVENT_STREAM_CAPACITY, Node, NodeEvent,
+    NodeEventStream, NodeStatusError, ReservedPeersError, SendRequestError, SubscribeError,
+    TopicSubscription, WeakNode,
 };
 pub use crate::node_runner::NodeRunner;
 pub use constructor::{Config, CreationError, KademliaMode, construct, peer_id};
 pub use libp2p;
-pub use shared::PeerDiscovered;
+pub use shared::{BandwidthStats, BootstrapStatus, NodeStatus, PeerDiscovered};
 pub use utils::PeerAddress;
 pub use utils::key_with_distance::KeyWithDistance;
 pub use utils::multihash::Multihash;
+pub use utils::peer_reputation::ReputationChange;
@@ -1,19 +1,26 @@
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 
 use crate::protocols::request_response::handlers::generic_request_handler::GenericRequest;
 use crate::protocols::request_response::request_response_factory;
-use crate::shared::{Command, CreatedSubscription, PeerDiscovered, Shared};
+use crate::shared::{
+    BandwidthStats, BootstrapStatus, Command, CreatedSubscription, NodeStatus, PeerDiscovered,
+    Shared,
+};
 use crate::utils::HandlerFn;
 use crate::utils::multihash::Multihash;
+use crate::utils::peer_reputation::ReputationChange;
 use bytes::Bytes;
 use event_listener_primitives::HandlerId;
 use futures::channel::{mpsc, oneshot};
 use futures::{SinkExt, Stream, StreamExt};
+use libp2p::autonat::NatStatus;
 use libp2p::gossipsub::{Sha256Topic, SubscriptionError};
 use libp2p::kad::{PeerRecord, RecordKey};
+use libp2p::multiaddr::Protocol;
 use libp2p::{Multiaddr, PeerId};
 use parity_scale_codec::Decode;
+use parking_lot::Mutex;
 use std::pin::Pin;
 use std::sync::{Arc, Weak};
 use std::task::{Context, Poll};
@@ -21,6 +28,41 @@ use thiserror::Error;
 use tokio::sync::OwnedSemaphorePermit;
 use tracing::{debug, trace};
 
+/// Maximum number of buffered events in [`NodeEventStream`], newer events are dropped when full
+pub const NODE_EVENT_STREAM_CAPACITY: usize = 1024;
+
+/// Events observed by the node, see [`Node::event_stream()`].
+#[derive(Debug, Clone)]
+pub enum NodeEvent {
+    /// First connection to a peer was established.
+    PeerConnected(PeerId),
+    /// Last connection to a peer was closed.
+    PeerDisconnected(PeerId),
+    /// Node started listening on a new address.
+    NewListenAddr(Multiaddr),
+    /// Peer was discovered by Kademlia.
+    PeerDiscovered(PeerDiscovered),
+}
+
+/// Stream of node events, stops receiving events when dropped.
+#[derive(Debug)]
+pub struct NodeEventStream {
+    _handler_ids: [HandlerId; 4],
+    receiver: mpsc::Receiver<NodeEvent>,
+}
+
+impl Stream for NodeEventStream {
+    type Item = NodeEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.receiver.size_hint()
+    }
+}
+
 /// Topic subscription, will unsubscribe when last instance is dropped for a particular topic.
 #[derive(Debug)]
 #[pin_project::pin_project(PinnedDrop)]
@@ -243,6 +285,63 @@ impl From<oneshot::Canceled> for ConnectedPeersError {
     }
 }
 
+/// Defines errors for reserved peers management.
+#[derive(Debug, Error)]
+pub enum ReservedPeersError {
+    /// Reserved peer address must end with peer ID
+    #[error("Reserved peer address must end with peer ID: {0}")]
+    MissingPeerId(Multiaddr),
+    /// Failed to send command to the node runner
+    #[error("Failed to send command to the node runner: {0}")]
+    SendCommand(#[from] mpsc::SendError),
+    /// Node runner was dropped
+    #[error("Node runner was dropped")]
+    NodeRunnerDropped,
+}
+
+impl From<oneshot::Canceled> for ReservedPeersError {
+    #[inline]
+    fn from(oneshot::Canceled: oneshot::Canceled) -> Self {
+        Self::NodeRunnerDropped
+    }
+}
+
+/// Defines errors for node status request.
+#[derive(Debug, Error)]
+pub enum NodeStatusError {
+    /// Failed to send command to the node runner
+    #[error("Failed to send command to the node runner: {0}")]
+    SendCommand(#[from] mpsc::SendError),
+    /// Node runner was dropped
+    #[error("Node runner was dropped")]
+    NodeRunnerDropped,
+}
+
+impl From<oneshot::Canceled> for NodeStatusError {
+    #[inline]
+    fn from(oneshot::Canceled: oneshot::Canceled) -> Self {
+        Self::NodeRunnerDropped
+    }
+}
+
+/// Defines errors for banned peers request.
+#[derive(Debug, Error)]
+pub enum BannedPeersError {
+    /// Failed to send command to the node runner
+    #[error("Failed to send command to the node runner: {0}")]
+    SendCommand(#[from] mpsc::SendError),
+    /// Node runner was dropped
+    #[error("Node runner was dropped")]
+    NodeRunnerDropped,
+}
+
+impl From<oneshot::Canceled> for BannedPeersError {
+    #[inline]
+    fn from(oneshot::Canceled: oneshot::Canceled) -> Self {
+        Self::NodeRunnerDropped
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum BootstrapError {
     /// Failed to send command to the node runner
@@ -529,6 +628,87 @@ impl Node {
             .await
     }
 
+    /// Report reputation change of the peer with specified peer ID.
+    ///
+    /// Peer is banned for [`Config::peer_ban_duration`] once its reputation drops to or below
+    /// [`Config::peer_ban_reputation_threshold`].
+    ///
+    /// [`Config::peer_ban_duration`]: crate::Config::peer_ban_duration
+    /// [`Config::peer_ban_reputation_threshold`]: crate::Config::peer_ban_reputation_threshold
+    pub async fn report_peer(
+        &self,
+        peer_id: PeerId,
+        change: ReputationChange,
+    ) -> Result<(), mpsc::SendError> {
+        self.shared
+            .command_sender
+            .clone()
+            .send(Command::ReportPeer { peer_id, change })
+            .await
+    }
+
+    /// Returns a collection of peers that are currently banned, either permanently or due to low
+    /// reputation.
+    pub async fn banned_peers(&self) -> Result<Vec<PeerId>, BannedPeersError> {
+        let (result_sender, result_receiver) = oneshot::channel();
+
+        trace!("Starting `banned_peers` request.");
+
+        self.shared
+            .command_sender
+            .clone()
+            .send(Command::BannedPeers { result_sender })
+            .await?;
+
+        Ok(result_receiver.await?)
+    }
+
+    /// Add reserved peer at runtime, `address` must end with `/p2p/<peer_id>`.
+    ///
+    /// Connection to reserved peer will be established and maintained.
+    pub async fn add_reserved_peer(&self, address: Multiaddr) -> Result<(), ReservedPeersError> {
+        let mut stripped_address = address.clone();
+        let Some(Protocol::P2p(peer_id)) = stripped_address.pop() else {
+            return Err(ReservedPeersError::MissingPeerId(address));
+        };
+
+        self.shared
+            .command_sender
+            .clone()
+            .send(Command::AddReservedPeer {
+                peer_id,
+                address: stripped_address,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Remove reserved peer at runtime, it will not be re-dialed and connection to it will be
+    /// allowed to close.
+    pub async fn remove_reserved_peer(&self, peer_id: PeerId) -> Result<(), ReservedPeersError> {
+        self.shared
+            .command_sender
+            .clone()
+            .send(Command::RemoveReservedPeer { peer_id })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns current reserved peers with their addresses.
+    pub async fn reserved_peers(&self) -> Result<Vec<Multiaddr>, ReservedPeersError> {
+        let (result_sender, result_receiver) = oneshot::channel();
+
+        self.shared
+            .command_sender
+            .clone()
+            .send(Command::ReservedPeers { result_sender })
+            .await?;
+
+        Ok(result_receiver.await?)
+    }
+
     /// Dial multiaddress.
     /// It could be used to test libp2p transports bypassing protocol checks for bootstrap
     /// or listen-on addresses.
@@ -551,6 +731,36 @@ impl Node {
         self.shared.external_addresses.lock().clone()
     }
 
+    /// NAT status detected by AutoNAT.
+    ///
+    /// AutoNAT only probes when Kademlia mode is dynamic and no external addresses were configured,
+    /// otherwise status remains [`NatStatus::Unknown`].
+    pub fn nat_status(&self) -> NatStatus {
+        self.shared.nat_status.lock().clone()
+    }
+
+    /// Reachability of bootstrap nodes that were dialed so far.
+    pub fn bootstrap_status(&self) -> BootstrapStatus {
+        let reachability = self.shared.bootstrap_nodes_reachability.lock();
+        let reachable = reachability
+            .values()
+            .filter(|reachable| **reachable)
+            .count();
+
+        BootstrapStatus {
+            reachable,
+            unreachable: reachability.len() - reachable,
+        }
+    }
+
+    /// Total number of bytes transferred over all connections since node was started.
+    pub fn bandwidth_stats(&self) -> BandwidthStats {
+        BandwidthStats {
+            inbound_bytes: self.shared.bandwidth_counters.inbound.get(),
+            outbound_bytes: self.shared.bandwidth_counters.outbound.get(),
+        }
+    }
+
     /// Callback is called when node starts listening on new address.
     pub fn on_new_listener(&self, callback: HandlerFn<Multiaddr>) -> HandlerId {
         self.shared.handlers.new_listener.add(callback)
@@ -584,6 +794,36 @@ impl Node {
             .map_err(|_| ConnectedPeersError::ConnectedPeers)
     }
 
+    /// Shut down the node, [`NodeRunner::run()`] returns after connections to all peers are closed
+    /// or after a few seconds if some of them don't close in time. This also interrupts initial
+    /// bootstrapping.
+    ///
+    /// Requests in progress fail, known peers are persisted once the runner is dropped.
+    ///
+    /// [`NodeRunner::run()`]: crate::NodeRunner::run
+    pub async fn shutdown(&self) -> Result<(), mpsc::SendError> {
+        self.shared
+            .command_sender
+            .clone()
+            .send(Command::Shutdown)
+            .await
+    }
+
+    /// Returns a point-in-time snapshot of the node state.
+    pub async fn status(&self) -> Result<NodeStatus, NodeStatusError> {
+        let (result_sender, result_receiver) = oneshot::channel();
+
+        trace!("Starting `status` request.");
+
+        self.shared
+            .command_sender
+            .clone()
+            .send(Command::Status { result_sender })
+            .await?;
+
+        Ok(result_receiver.await?)
+    }
+
     /// Returns a collection of currently connected servers (typically farmers).
     pub async fn connected_servers(&self) -> Result<Vec<PeerId>, ConnectedPeersError> {
         let (result_sender, result_receiver) = oneshot::channel();
@@ -643,6 +883,52 @@ impl Node {
         self.shared.handlers.peer_discovered.add(callback)
     }
 
+    /// Stream of peer connection, listener and peer discovery events.
+    ///
+    /// Events are buffered in a bounded channel of [`NODE_EVENT_STREAM_CAPACITY`] events, so a slow
+    /// consumer never blocks the node runner. Events that don't fit into the buffer are dropped,
+    /// consumers must keep up with the stream if they need to observe every event.
+    pub fn event_stream(&self) -> NodeEventStream {
+        let (sender, receiver) = mpsc::channel(NODE_EVENT_STREAM_CAPACITY);
+        let sender = Arc::new(Mutex::new(sender));
+        let handlers = &self.shared.handlers;
+
+        let handler_ids = [
+            handlers.connected_peer.add(Arc::new({
+                let sender = Arc::clone(&sender);
+                move |peer_id| {
+                    let _ = sender.lock().try_send(NodeEvent::PeerConnected(*peer_id));
+                }
+            })),
+            handlers.disconnected_peer.add(Arc::new({
+                let sender = Arc::clone(&sender);
+                move |peer_id| {
+                    let _ = sender
+                        .lock()
+                        .try_send(NodeEvent::PeerDisconnected(*peer_id));
+                }
+            })),
+            handlers.new_listener.add(Arc::new({
+                let sender = Arc::clone(&sender);
+                move |address| {
+                    let _ = sender
+                        .lock()
+                        .try_send(NodeEvent::NewListenAddr(address.clone()));
+                }
+            })),
+            handlers.peer_discovered.add(Arc::new(move |peer| {
+                let _ = sender
+                    .lock()
+                    .try_send(NodeEvent::PeerDiscovered(peer.clone()));
+            })),
+        ];
+
+        NodeEventStream {
+            _handler_ids: handler_ids,
+            receiver,
+        }
+    }
+
     /// Returns the request batch handle with common "connection permit" slot from the shared pool.
     pub async fn get_requests_batch_handle(&self) -> NodeRequestsBatchHandle {
         let _permit = self.shared.rate_limiter.acquire_permit().await;
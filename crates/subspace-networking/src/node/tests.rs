@@ -1,12 +1,34 @@
 use crate::protocols::request_response::handlers::generic_request_handler::{
     GenericRequest, GenericRequestHandler,
 };
-use crate::{Config, construct};
+use crate::protocols::request_response::handlers::piece_by_index::{
+    PieceByIndexRequest, PieceByIndexRequestHandler, PieceByIndexResponse,
+};
+use crate::protocols::request_response::handlers::segment_header::{
+    SegmentHeaderBySegmentIndexesRequestHandler, SegmentHeaderRequest, SegmentHeaderResponse,
+};
+use crate::protocols::request_response::request_response_factory::RequestFailure;
+use crate::utils::{SubspaceMetrics, strip_peer_id};
+use crate::{
+    BootstrapStatus, Config, NODE_EVENT_STREAM_CAPACITY, Node, NodeEvent, NodeEventStream,
+    ReputationChange, SendRequestError, construct,
+};
 use futures::channel::oneshot;
+use futures::{FutureExt, StreamExt};
+use libp2p::autonat::{Event as AutonatEvent, NatStatus};
 use libp2p::multiaddr::Protocol;
+use libp2p::{Multiaddr, PeerId};
 use parity_scale_codec::{Decode, Encode};
 use parking_lot::Mutex;
+use prometheus_client::encoding::text::encode;
+use prometheus_client::registry::Registry;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use subspace_core_primitives::hashes::Blake3Hash;
+use subspace_core_primitives::pieces::{Piece, PieceIndex};
+use subspace_core_primitives::segments::{
+    ArchivedBlockProgress, LastArchivedBlock, SegmentCommitment, SegmentHeader, SegmentIndex,
+};
 use subspace_process::init_logger;
 
 #[derive(Encode, Decode)]
@@ -21,6 +43,43 @@ impl GenericRequest for ExampleRequest {
 #[derive(Encode, Decode, Debug)]
 struct ExampleResponse;
 
+/// Start two nodes and wait for the first one to know its address.
+///
+/// Config of the second node is created from the first node and its address. Nodes are not
+/// connected until the second node dials or sends a request to the returned address of the first
+/// node (or uses it as a bootstrap address).
+pub(crate) async fn start_two_nodes<Config2>(
+    config_1: Config,
+    config_2: Config2,
+) -> (Node, Multiaddr, Node)
+where
+    Config2: FnOnce(&Node, &Multiaddr) -> Config,
+{
+    let (node_1, mut node_runner_1) = construct(config_1).unwrap();
+    let mut node_1_events = node_1.event_stream();
+
+    tokio::spawn(async move {
+        node_runner_1.run().await;
+    });
+
+    // Wait for first node to know its address
+    let node_1_addr = loop {
+        if let NodeEvent::NewListenAddr(address) = node_1_events.next().await.unwrap()
+            && matches!(address.iter().next(), Some(Protocol::Ip4(_)))
+        {
+            break address;
+        }
+    };
+
+    let (node_2, mut node_runner_2) = construct(config_2(&node_1, &node_1_addr)).unwrap();
+
+    tokio::spawn(async move {
+        node_runner_2.run().await;
+    });
+
+    (node_1, node_1_addr, node_2)
+}
+
 #[tokio::test]
 async fn request_with_addresses() {
     init_logger();
@@ -84,3 +143,535 @@ async fn request_with_addresses() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn segment_header_request() {
+    init_logger();
+
+    let segment_header = SegmentHeader::V0 {
+        segment_index: SegmentIndex::ZERO,
+        segment_commitment: SegmentCommitment::default(),
+        prev_segment_header_hash: Blake3Hash::default(),
+        last_archived_block: LastArchivedBlock {
+            number: 1,
+            archived_progress: ArchivedBlockProgress::Complete,
+        },
+    };
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![SegmentHeaderBySegmentIndexesRequestHandler::create(
+            move |_, request| async move {
+                let segment_indexes = match request {
+                    SegmentHeaderRequest::SegmentIndexes { segment_indexes } => segment_indexes,
+                    SegmentHeaderRequest::LastSegmentHeaders { .. } => {
+                        return Some(SegmentHeaderResponse {
+                            segment_headers: vec![segment_header],
+                        });
+                    }
+                };
+
+                // Unknown segment indexes result in no response
+                segment_indexes
+                    .iter()
+                    .map(|segment_index| {
+                        (*segment_index == segment_header.segment_index()).then_some(segment_header)
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .map(|segment_headers| SegmentHeaderResponse { segment_headers })
+            },
+        )],
+        ..Config::default()
+    };
+
+    let config_2 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![SegmentHeaderBySegmentIndexesRequestHandler::create(
+            |_, _| async { None },
+        )],
+        ..Config::default()
+    };
+
+    let (node_1, node_1_addr, node_2) = start_two_nodes(config_1, |_, _| config_2).await;
+
+    let response = node_2
+        .send_generic_request(
+            node_1.id(),
+            vec![node_1_addr],
+            SegmentHeaderRequest::SegmentIndexes {
+                segment_indexes: Arc::new(vec![SegmentIndex::ZERO]),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.segment_headers, vec![segment_header]);
+
+    // Unknown segment index must not be answered
+    assert!(
+        node_2
+            .send_generic_request(
+                node_1.id(),
+                Vec::new(),
+                SegmentHeaderRequest::SegmentIndexes {
+                    segment_indexes: Arc::new(vec![SegmentIndex::ONE]),
+                },
+            )
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn inbound_request_metrics() {
+    init_logger();
+
+    let mut registry = Registry::default();
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _example_request| async { Some(ExampleResponse) },
+        )],
+        metrics: Some(SubspaceMetrics::new(&mut registry)),
+        ..Config::default()
+    };
+
+    let config_2 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _| async { None },
+        )],
+        ..Config::default()
+    };
+
+    let (node_1, node_1_addr, node_2) = start_two_nodes(config_1, |_, _| config_2).await;
+
+    node_2
+        .send_generic_request(node_1.id(), vec![node_1_addr], ExampleRequest)
+        .await
+        .unwrap();
+
+    // Statistics event is processed by the node runner asynchronously after the response is sent
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let mut metrics = String::new();
+            encode(&mut metrics, &registry).unwrap();
+
+            if metrics.contains(r#"subspace_inbound_requests_total{protocol="/example"} 1"#) {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn bootstrap_status() {
+    init_logger();
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        ..Config::default()
+    };
+
+    // Second node bootstraps from the first node and an unreachable address
+    let (_node_1, _node_1_addr, node_2) = start_two_nodes(config_1, |node_1, node_1_addr| Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        bootstrap_addresses: vec![
+            node_1_addr.clone().with(Protocol::P2p(node_1.id())),
+            format!("/ip4/127.0.0.1/tcp/1/p2p/{}", PeerId::random())
+                .parse()
+                .unwrap(),
+        ],
+        ..Config::default()
+    })
+    .await;
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            if node_2.bootstrap_status()
+                == (BootstrapStatus {
+                    reachable: 1,
+                    unreachable: 1,
+                })
+            {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn request_timeout() {
+    init_logger();
+
+    let request_timeout = Duration::from_secs(1);
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _example_request| futures::future::pending(),
+        )],
+        ..Config::default()
+    };
+
+    let config_2 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _| async { None },
+        )],
+        request_timeout,
+        ..Config::default()
+    };
+
+    let (node_1, node_1_addr, node_2) = start_two_nodes(config_1, |_, _| config_2).await;
+
+    let started = Instant::now();
+    let result = node_2
+        .send_generic_request(node_1.id(), vec![node_1_addr], ExampleRequest)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(SendRequestError::ProtocolFailure(RequestFailure::Timeout))
+    ));
+    // Allow some time for connection establishment on top of request timeout
+    assert!(started.elapsed() < request_timeout * 5);
+}
+
+#[tokio::test]
+async fn event_stream_peer_connected() {
+    init_logger();
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _example_request| async { Some(ExampleResponse) },
+        )],
+        ..Config::default()
+    };
+
+    let config_2 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _| async { None },
+        )],
+        ..Config::default()
+    };
+
+    let (node_1, node_1_addr, node_2) = start_two_nodes(config_1, |_, _| config_2).await;
+    let mut node_1_events = node_1.event_stream();
+    let mut node_2_events = node_2.event_stream();
+
+    node_2
+        .send_generic_request(node_1.id(), vec![node_1_addr], ExampleRequest)
+        .await
+        .unwrap();
+
+    async fn wait_for_peer_connected(events: &mut NodeEventStream, expected_peer_id: PeerId) {
+        loop {
+            if let NodeEvent::PeerConnected(peer_id) = events.next().await.unwrap()
+                && peer_id == expected_peer_id
+            {
+                break;
+            }
+        }
+    }
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        wait_for_peer_connected(&mut node_1_events, node_2.id()).await;
+        wait_for_peer_connected(&mut node_2_events, node_1.id()).await;
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn event_stream_drops_events_on_overflow() {
+    init_logger();
+
+    let (node, _node_runner) = construct(Config::default()).unwrap();
+    let mut node_events = node.event_stream();
+
+    // Nobody reads events while they are emitted, so only as many as fit into the buffer are kept
+    let address = "/ip4/127.0.0.1/tcp/30333".parse::<Multiaddr>().unwrap();
+    for _ in 0..NODE_EVENT_STREAM_CAPACITY * 2 {
+        node.shared.handlers.new_listener.call_simple(&address);
+    }
+
+    let mut received_events = 0;
+    while let Some(Some(event)) = node_events.next().now_or_never() {
+        assert!(matches!(event, NodeEvent::NewListenAddr(_)));
+        received_events += 1;
+    }
+    assert!(received_events >= NODE_EVENT_STREAM_CAPACITY);
+    assert!(received_events < NODE_EVENT_STREAM_CAPACITY * 2);
+}
+
+#[tokio::test]
+async fn nat_status() {
+    init_logger();
+
+    let (node, mut node_runner) = construct(Config::default()).unwrap();
+
+    // Unknown until autonat probes are done
+    assert_eq!(node.nat_status(), NatStatus::Unknown);
+
+    let public_address = "/ip4/1.1.1.1/tcp/30333".parse::<Multiaddr>().unwrap();
+    node_runner
+        .handle_autonat_event(AutonatEvent::StatusChanged {
+            old: NatStatus::Unknown,
+            new: NatStatus::Public(public_address.clone()),
+        })
+        .await;
+    assert_eq!(node.nat_status(), NatStatus::Public(public_address.clone()));
+
+    node_runner
+        .handle_autonat_event(AutonatEvent::StatusChanged {
+            old: NatStatus::Public(public_address),
+            new: NatStatus::Private,
+        })
+        .await;
+    assert_eq!(node.nat_status(), NatStatus::Private);
+}
+
+#[tokio::test]
+async fn node_status() {
+    init_logger();
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _example_request| async { Some(ExampleResponse) },
+        )],
+        ..Config::default()
+    };
+
+    let config_2 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _| async { None },
+        )],
+        ..Config::default()
+    };
+
+    let (node_1, node_1_addr, node_2) = start_two_nodes(config_1, |_, _| config_2).await;
+
+    node_2
+        .send_generic_request(node_1.id(), vec![node_1_addr], ExampleRequest)
+        .await
+        .unwrap();
+
+    let status = node_2.status().await.unwrap();
+    assert_eq!(status.connected_peers, 1);
+    assert_eq!(status.connected_reserved_peers, 0);
+    assert!(!status.listeners.is_empty());
+}
+
+#[tokio::test]
+async fn shutdown() {
+    init_logger();
+
+    let config = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        ..Config::default()
+    };
+    let (node, mut node_runner) = construct(config).unwrap();
+
+    let node_runner_handle = tokio::spawn(async move {
+        node_runner.run().await;
+    });
+
+    node.shutdown().await.unwrap();
+
+    // Runner must exit even though node instance is still alive
+    tokio::time::timeout(Duration::from_secs(5), node_runner_handle)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(node.status().await.is_err());
+}
+
+#[tokio::test]
+async fn report_peer_bans_after_enough_violations() {
+    init_logger();
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _example_request| async { Some(ExampleResponse) },
+        )],
+        peer_ban_reputation_threshold: -200,
+        ..Config::default()
+    };
+
+    let config_2 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![GenericRequestHandler::<ExampleRequest>::create(
+            |_, _| async { None },
+        )],
+        ..Config::default()
+    };
+
+    let (node_1, node_1_addr, node_2) = start_two_nodes(config_1, |_, _| config_2).await;
+
+    node_2
+        .send_generic_request(node_1.id(), vec![node_1_addr.clone()], ExampleRequest)
+        .await
+        .unwrap();
+
+    node_1
+        .report_peer(node_2.id(), ReputationChange::INVALID_DATA)
+        .await
+        .unwrap();
+    assert!(node_1.banned_peers().await.unwrap().is_empty());
+
+    node_1
+        .report_peer(node_2.id(), ReputationChange::INVALID_DATA)
+        .await
+        .unwrap();
+    assert_eq!(node_1.banned_peers().await.unwrap(), vec![node_2.id()]);
+
+    // Banned peer is disconnected and can't reconnect
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !node_2.connected_peers().await.unwrap().is_empty() {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    assert!(
+        node_2
+            .send_generic_request(node_1.id(), vec![node_1_addr], ExampleRequest)
+            .await
+            .is_err()
+    );
+    assert!(node_1.connected_peers().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn dns_bootstrap_address() {
+    init_logger();
+
+    let bootstrap_peer_id = PeerId::random();
+    let bootstrap_address =
+        format!("/dns4/bootstrap.example.com/tcp/30533/p2p/{bootstrap_peer_id}")
+            .parse::<Multiaddr>()
+            .unwrap();
+
+    assert_eq!(
+        strip_peer_id(vec![bootstrap_address.clone()]),
+        vec![(
+            bootstrap_peer_id,
+            "/dns4/bootstrap.example.com/tcp/30533".parse().unwrap()
+        )]
+    );
+
+    let config = Config {
+        bootstrap_addresses: vec![bootstrap_address],
+        ..Config::default()
+    };
+    construct(config).unwrap();
+}
+
+#[tokio::test]
+async fn listeners_report_bound_port() {
+    init_logger();
+
+    let config = Config {
+        listen_on: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        ..Config::default()
+    };
+    let (node, mut node_runner) = construct(config).unwrap();
+    let mut node_events = node.event_stream();
+
+    assert!(node.listeners().is_empty());
+
+    tokio::spawn(async move {
+        node_runner.run().await;
+    });
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !matches!(node_events.next().await, Some(NodeEvent::NewListenAddr(_))) {}
+    })
+    .await
+    .unwrap();
+
+    let listeners = node.listeners();
+    assert_eq!(listeners.len(), 1);
+    assert!(
+        listeners[0]
+            .iter()
+            .any(|protocol| matches!(protocol, Protocol::Tcp(port) if port != 0))
+    );
+}
+
+#[tokio::test]
+async fn bandwidth_stats() {
+    init_logger();
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![PieceByIndexRequestHandler::create(|_, _| async {
+            Some(PieceByIndexResponse {
+                piece: Some(Piece::default()),
+                cached_pieces: Vec::new(),
+            })
+        })],
+        ..Config::default()
+    };
+
+    let config_2 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![PieceByIndexRequestHandler::create(|_, _| async { None })],
+        ..Config::default()
+    };
+
+    let (node_1, node_1_addr, node_2) = start_two_nodes(config_1, |_, _| config_2).await;
+
+    let outbound_bytes_before = node_1.bandwidth_stats().outbound_bytes;
+
+    let response = node_2
+        .send_generic_request(
+            node_1.id(),
+            vec![node_1_addr],
+            PieceByIndexRequest {
+                piece_index: PieceIndex::ZERO,
+                cached_pieces: Arc::default(),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(response.piece.is_some());
+
+    let node_1_stats = node_1.bandwidth_stats();
+    let node_2_stats = node_2.bandwidth_stats();
+    assert!(node_1_stats.outbound_bytes - outbound_bytes_before >= Piece::SIZE as u64);
+    assert!(node_2_stats.inbound_bytes >= Piece::SIZE as u64);
+}
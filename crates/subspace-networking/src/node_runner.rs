@@ -9,7 +9,8 @@ use crate::constructor::temporary_bans::TemporaryBans;
 use crate::protocols::request_response::request_response_factory::{
     Event as RequestResponseEvent, IfDisconnected,
 };
-use crate::shared::{Command, CreatedSubscription, PeerDiscovered, Shared};
+use crate::shared::{Command, CreatedSubscription, NodeStatus, PeerDiscovered, Shared};
+use crate::utils::peer_reputation::{PeerReputations, ReportOutcome, ReputationChange};
 use crate::utils::{SubspaceMetrics, is_global_address_or_dns, strip_peer_id};
 use async_lock::Mutex as AsyncMutex;
 use bytes::Bytes;
@@ -37,6 +38,7 @@ use parking_lot::Mutex;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::net::IpAddr;
+use std::ops::ControlFlow;
 use std::pin::Pin;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Weak};
@@ -59,6 +61,9 @@ const PEER_INFO_LOG_INTERVAL: Duration = Duration::from_secs(300);
 /// The maximum number of OS-reported listener addresses we will use.
 const MAX_LISTEN_ADDRESSES: usize = 30;
 
+/// The maximum time to wait for connections to close during graceful shutdown.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 enum QueryResultSender {
     Value {
         sender: mpsc::UnboundedSender<PeerRecord>,
@@ -125,6 +130,8 @@ pub struct NodeRunner {
     reserved_peers: HashMap<PeerId, Multiaddr>,
     /// Temporarily banned peers.
     temporary_bans: Arc<Mutex<TemporaryBans>>,
+    /// Reputation of peers and peers banned due to low reputation.
+    peer_reputations: PeerReputations,
     /// Libp2p Prometheus metrics.
     libp2p_metrics: Option<Metrics>,
     /// Subspace Prometheus metrics.
@@ -135,6 +142,8 @@ pub struct NodeRunner {
     protocol_version: String,
     /// Addresses to bootstrap Kademlia network
     bootstrap_addresses: Vec<Multiaddr>,
+    /// Peer IDs of bootstrap nodes, used to track their reachability
+    bootstrap_node_ids: HashSet<PeerId>,
     /// Ensures a single bootstrap on run() invocation.
     bootstrap_command_state: Arc<AsyncMutex<BootstrapCommandState>>,
     /// Receives an event on peer address removal from the persistent storage.
@@ -163,6 +172,7 @@ pub(crate) struct NodeRunnerConfig {
     pub(crate) known_peers_registry: Box<dyn KnownPeersRegistry>,
     pub(crate) reserved_peers: HashMap<PeerId, Multiaddr>,
     pub(crate) temporary_bans: Arc<Mutex<TemporaryBans>>,
+    pub(crate) peer_reputations: PeerReputations,
     pub(crate) libp2p_metrics: Option<Metrics>,
     pub(crate) metrics: Option<SubspaceMetrics>,
     pub(crate) protocol_version: String,
@@ -181,6 +191,7 @@ impl NodeRunner {
             mut known_peers_registry,
             reserved_peers,
             temporary_bans,
+            peer_reputations,
             libp2p_metrics,
             metrics,
             protocol_version,
@@ -220,10 +231,15 @@ impl NodeRunner {
             connected_servers: HashSet::new(),
             reserved_peers,
             temporary_bans,
+            peer_reputations,
             libp2p_metrics,
             metrics,
             peer_ip_addresses: HashMap::new(),
             protocol_version,
+            bootstrap_node_ids: strip_peer_id(bootstrap_addresses.clone())
+                .into_iter()
+                .map(|(peer_id, _)| peer_id)
+                .collect(),
             bootstrap_addresses,
             bootstrap_command_state: Arc::new(AsyncMutex::new(BootstrapCommandState::default())),
             removed_addresses_rx,
@@ -250,7 +266,9 @@ impl NodeRunner {
             }
         }
 
-        self.bootstrap().await;
+        if self.bootstrap().await.is_break() {
+            return;
+        }
 
         loop {
             futures::select! {
@@ -271,10 +289,17 @@ impl NodeRunner {
                     }
                 },
                 command = self.command_receiver.next() => {
-                    if let Some(command) = command {
-                        self.handle_command(command);
-                    } else {
-                        break;
+                    match command {
+                        Some(Command::Shutdown) => {
+                            self.shutdown().await;
+                            break;
+                        }
+                        Some(command) => {
+                            self.handle_command(command);
+                        }
+                        None => {
+                            break;
+                        }
                     }
                 },
                 _ = self.known_peers_registry.run().fuse() => {
@@ -295,14 +320,25 @@ impl NodeRunner {
         }
     }
 
-    /// Bootstraps Kademlia network
-    async fn bootstrap(&mut self) {
+    /// Bootstraps Kademlia network.
+    ///
+    /// Returns [`ControlFlow::Break`] if the node was shut down during bootstrapping.
+    async fn bootstrap(&mut self) -> ControlFlow<()> {
         // Add bootstrap nodes first to make sure there is space for them in k-buckets
         for (peer_id, address) in strip_peer_id(self.bootstrap_addresses.clone()) {
             self.swarm
                 .behaviour_mut()
                 .kademlia
-                .add_address(&peer_id, address);
+                .add_address(&peer_id, address.clone());
+
+            // Dial explicitly to find out early whether bootstrap node is reachable
+            if let Err(error) = self
+                .swarm
+                .dial(DialOpts::peer_id(peer_id).addresses(vec![address]).build())
+            {
+                warn!(%peer_id, %error, "Failed to dial bootstrap node");
+                self.update_bootstrap_node_reachability(peer_id, false);
+            }
         }
 
         let known_peers = self.known_peers_registry.all_known_peers().await;
@@ -335,7 +371,7 @@ impl NodeRunner {
             self.handle_command(Command::Bootstrap {
                 result_sender: None,
             });
-            return;
+            return ControlFlow::Continue(());
         }
 
         let bootstrap_command_state = self.bootstrap_command_state.clone();
@@ -365,10 +401,12 @@ impl NodeRunner {
                 bootstrap_command_receiver
             }
             BootstrapCommandState::Finished => {
-                return;
+                return ControlFlow::Continue(());
             }
         };
 
+        // Other commands are handled once bootstrapping is finished, in the order they were received
+        let mut pending_commands = Vec::new();
         let mut bootstrap_step = 0;
         loop {
             futures::select! {
@@ -387,12 +425,32 @@ impl NodeRunner {
                     } else {
                         break;
                     }
-                }
+                },
+                command = self.command_receiver.next() => {
+                    match command {
+                        Some(Command::Shutdown) => {
+                            self.shutdown().await;
+                            return ControlFlow::Break(());
+                        }
+                        Some(command) => {
+                            pending_commands.push(command);
+                        }
+                        None => {
+                            return ControlFlow::Break(());
+                        }
+                    }
+                },
             }
         }
 
         debug!("Bootstrap finished.");
         *bootstrap_command_state = BootstrapCommandState::Finished;
+
+        for command in pending_commands {
+            self.handle_command(command);
+        }
+
+        ControlFlow::Continue(())
     }
 
     /// Handles periodical tasks.
@@ -413,6 +471,12 @@ impl NodeRunner {
             addresses.append(&mut external_addresses);
         }
 
+        for peer_id in self.peer_reputations.remove_expired_bans() {
+            debug!(%peer_id, "Peer reputation ban expired");
+
+            self.swarm.behaviour_mut().block_list.unblock_peer(peer_id);
+        }
+
         self.log_kademlia_stats();
     }
 
@@ -542,6 +606,10 @@ impl NodeRunner {
                     "Connection established"
                 );
 
+                if self.update_bootstrap_node_reachability(peer_id, true) {
+                    info!(%peer_id, ?endpoint, "Bootstrap node is reachable");
+                }
+
                 let maybe_remote_ip =
                     endpoint
                         .get_remote_address()
@@ -641,6 +709,12 @@ impl NodeRunner {
                     "SwarmEvent::OutgoingConnectionError for peer."
                 );
 
+                if let Some(peer_id) = peer_id
+                    && self.update_bootstrap_node_reachability(peer_id, false)
+                {
+                    warn!(%peer_id, %error, "Bootstrap node is unreachable");
+                }
+
                 match error {
                     DialError::Transport(ref addresses) => {
                         for (addr, _) in addresses {
@@ -686,6 +760,34 @@ impl NodeRunner {
         }
     }
 
+    /// Records reachability of a bootstrap node, returns `true` if it has changed.
+    ///
+    /// Once a bootstrap node was reachable, later dial failures don't mark it as unreachable.
+    fn update_bootstrap_node_reachability(&self, peer_id: PeerId, reachable: bool) -> bool {
+        if !self.bootstrap_node_ids.contains(&peer_id) {
+            return false;
+        }
+
+        let Some(shared) = self.shared_weak.upgrade() else {
+            return false;
+        };
+
+        let mut reachability = shared.bootstrap_nodes_reachability.lock();
+        match reachability.entry(peer_id) {
+            Entry::Occupied(mut entry) => {
+                if *entry.get() || !reachable {
+                    return false;
+                }
+                entry.insert(true);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(reachable);
+            }
+        }
+
+        true
+    }
+
     fn should_temporary_ban_on_dial_error(&self, peer_id: &PeerId, error: &DialError) -> bool {
         // TODO: Replace with banning of addresses rather peer IDs if this helps
         if true {
@@ -1195,11 +1297,19 @@ impl NodeRunner {
     }
 
     async fn handle_request_response_event(&mut self, event: RequestResponseEvent) {
-        // No actions on statistics events.
+        // Statistics events are only used for metrics.
         trace!("Request response event: {:?}", event);
+
+        if let Some(metrics) = &self.metrics
+            && let RequestResponseEvent::InboundRequest {
+                protocol, result, ..
+            } = &event
+        {
+            metrics.inc_inbound_requests(protocol, result.is_ok());
+        }
     }
 
-    async fn handle_autonat_event(&mut self, event: AutonatEvent) {
+    pub(crate) async fn handle_autonat_event(&mut self, event: AutonatEvent) {
         trace!(?event, "Autonat event received.");
         let autonat = &self.swarm.behaviour().autonat;
         debug!(
@@ -1250,6 +1360,10 @@ impl NodeRunner {
             AutonatEvent::StatusChanged { old, new } => {
                 debug!(?old, ?new, "Public address status changed.");
 
+                if let Some(shared) = self.shared_weak.upgrade() {
+                    *shared.nat_status.lock() = new.clone();
+                }
+
                 // TODO: Remove block once https://github.com/libp2p/rust-libp2p/issues/4863 is resolved
                 if let (NatStatus::Public(old_address), NatStatus::Private) = (old, new.clone()) {
                     self.swarm.remove_external_address(&old_address);
@@ -1497,14 +1611,76 @@ impl NodeRunner {
             Command::BanPeer { peer_id } => {
                 self.ban_peer(peer_id);
             }
+            Command::ReportPeer { peer_id, change } => {
+                self.report_peer(peer_id, change);
+            }
+            Command::BannedPeers { result_sender } => {
+                let banned_peers = self
+                    .swarm
+                    .behaviour()
+                    .block_list
+                    .blocked_peers()
+                    .iter()
+                    .copied()
+                    .collect();
+
+                let _ = result_sender.send(banned_peers);
+            }
             Command::Dial { address } => {
                 let _ = self.swarm.dial(address);
             }
+            Command::AddReservedPeer { peer_id, address } => {
+                self.reserved_peers.insert(peer_id, address.clone());
+                self.swarm
+                    .behaviour_mut()
+                    .reserved_peers
+                    .add_reserved_peer(peer_id, address);
+            }
+            Command::RemoveReservedPeer { peer_id } => {
+                self.reserved_peers.remove(&peer_id);
+                self.swarm
+                    .behaviour_mut()
+                    .reserved_peers
+                    .remove_reserved_peer(&peer_id);
+            }
+            Command::ReservedPeers { result_sender } => {
+                let reserved_peers = self
+                    .reserved_peers
+                    .iter()
+                    .map(|(peer_id, address)| address.clone().with(Protocol::P2p(*peer_id)))
+                    .collect();
+
+                let _ = result_sender.send(reserved_peers);
+            }
             Command::ConnectedPeers { result_sender } => {
                 let connected_peers = self.swarm.connected_peers().cloned().collect();
 
                 let _ = result_sender.send(connected_peers);
             }
+            Command::Shutdown => {
+                // Handled in `run()` since it needs to exit the main loop
+            }
+            Command::Status { result_sender } => {
+                let connected_reserved_peers = self
+                    .reserved_peers
+                    .keys()
+                    .filter(|peer_id| self.swarm.is_connected(peer_id))
+                    .count();
+                let routing_table_size = self
+                    .swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .kbuckets()
+                    .map(|kbucket| kbucket.num_entries())
+                    .sum();
+
+                let _ = result_sender.send(NodeStatus {
+                    connected_peers: self.swarm.connected_peers().count(),
+                    connected_reserved_peers,
+                    routing_table_size,
+                    listeners: self.swarm.listeners().cloned().collect(),
+                });
+            }
             Command::ConnectedServers { result_sender } => {
                 let connected_servers = self.connected_servers.iter().cloned().collect();
 
@@ -1532,9 +1708,42 @@ impl NodeRunner {
         }
     }
 
+    /// Closes connections to all peers before the runner exits.
+    ///
+    /// Swarm is polled until all connections are closed or [`SHUTDOWN_TIMEOUT`] elapses.
+    async fn shutdown(&mut self) {
+        debug!("Shutting down node runner");
+
+        let connected_peers = self.swarm.connected_peers().copied().collect::<Vec<_>>();
+        for peer_id in connected_peers {
+            let _ = self.swarm.disconnect_peer_id(peer_id);
+        }
+
+        let wait_for_connections_to_close = async {
+            while self.swarm.connected_peers().next().is_some() {
+                let Some(swarm_event) = self.swarm.next().await else {
+                    break;
+                };
+                self.register_event_metrics(&swarm_event);
+                self.handle_swarm_event(swarm_event).await;
+            }
+        };
+
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, wait_for_connections_to_close)
+            .await
+            .is_err()
+        {
+            debug!(
+                connected_peers = %self.swarm.connected_peers().count(),
+                "Timed out waiting for connections to close"
+            );
+        }
+    }
+
     fn ban_peer(&mut self, peer_id: PeerId) {
-        // Remove temporary ban if there is one, before creating a permanent one.
+        // Remove temporary bans if there are any, before creating a permanent one.
         self.temporary_bans.lock().remove(&peer_id);
+        self.peer_reputations.remove(&peer_id);
 
         debug!(?peer_id, "Banning peer on network level");
 
@@ -1547,6 +1756,41 @@ impl NodeRunner {
         let _ = self.swarm.disconnect_peer_id(peer_id);
     }
 
+    fn report_peer(&mut self, peer_id: PeerId, change: ReputationChange) {
+        let block_list = &mut self.swarm.behaviour_mut().block_list;
+        if block_list.blocked_peers().contains(&peer_id)
+            && !self.peer_reputations.is_banned(&peer_id)
+        {
+            // Peer is banned permanently, reputation doesn't matter anymore
+            return;
+        }
+
+        match self.peer_reputations.report(peer_id, change) {
+            ReportOutcome::Updated(reputation) => {
+                trace!(%peer_id, reason = change.reason, %reputation, "Peer reputation changed");
+            }
+            ReportOutcome::Banned { evicted_ban } => {
+                debug!(
+                    %peer_id,
+                    reason = change.reason,
+                    "Banning peer due to low reputation"
+                );
+
+                if let Some(evicted_peer_id) = evicted_ban {
+                    debug!(%evicted_peer_id, "Peer reputation ban evicted early");
+
+                    block_list.unblock_peer(evicted_peer_id);
+                }
+                block_list.block_peer(peer_id);
+                // Immediately disconnect the peer to cancel any in-flight requests.
+                let _ = self.swarm.disconnect_peer_id(peer_id);
+            }
+            ReportOutcome::AlreadyBanned => {
+                // Nothing to do
+            }
+        }
+    }
+
     fn register_event_metrics(&mut self, swarm_event: &SwarmEvent<Event>) {
         if let Some(ref mut metrics) = self.libp2p_metrics {
             match swarm_event {
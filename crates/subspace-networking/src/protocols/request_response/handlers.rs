@@ -3,4 +3,5 @@
 pub mod cached_piece_by_index;
 pub mod generic_request_handler;
 pub mod piece_by_index;
+pub mod piece_range;
 pub mod segment_header;
@@ -0,0 +1,49 @@
+//! Helper for incoming piece range requests.
+//!
+//! Request handler can be created with [`PieceRangeRequestHandler`].
+
+use crate::protocols::request_response::handlers::generic_request_handler::{
+    GenericRequest, GenericRequestHandler,
+};
+use parity_scale_codec::{Decode, Encode};
+use subspace_core_primitives::pieces::{Piece, PieceIndex};
+
+/// Piece range request, used to download contiguous range of pieces from a single peer.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct PieceRangeRequest {
+    /// Index of the first piece in the range
+    pub start_index: PieceIndex,
+    /// Number of pieces in the range, responder will not return more than
+    /// [`PieceRangeRequest::MAX_PIECES`] pieces
+    pub count: u32,
+}
+
+impl GenericRequest for PieceRangeRequest {
+    const PROTOCOL_NAME: &'static str = "/subspace/piece-range/0.1.0";
+    const LOG_TARGET: &'static str = "piece-range-request-response-handler";
+    type Response = PieceRangeResponse;
+}
+
+impl PieceRangeRequest {
+    /// Max number of pieces to return per request, keeps response well within max response size
+    pub const MAX_PIECES: u32 = 8;
+
+    /// Piece indexes within requested range, limited to [`PieceRangeRequest::MAX_PIECES`]
+    pub fn piece_indexes(&self) -> impl Iterator<Item = PieceIndex> + use<> {
+        let count = u64::from(self.count.min(Self::MAX_PIECES));
+
+        self.start_index..PieceIndex::new(u64::from(self.start_index).saturating_add(count))
+    }
+}
+
+/// Piece range response
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode)]
+pub struct PieceRangeResponse {
+    /// Pieces within requested range that responder has, in ascending order
+    pub pieces: Vec<(PieceIndex, Piece)>,
+    /// Pieces within requested range that responder doesn't have
+    pub missing: Vec<PieceIndex>,
+}
+
+/// Piece range request handler
+pub type PieceRangeRequestHandler = GenericRequestHandler<PieceRangeRequest>;
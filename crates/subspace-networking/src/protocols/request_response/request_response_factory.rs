@@ -104,11 +104,6 @@ pub struct ProtocolConfig {
     /// much memory for it.
     pub max_response_size: u64,
 
-    /// Duration after which emitted requests are considered timed out.
-    ///
-    /// If you expect the response to come back quickly, you should set this to a smaller duration.
-    pub request_timeout: Duration,
-
     /// Channel on which the networking service will send incoming requests.
     ///
     /// Every time a peer sends a request to the local node using this protocol, the networking
@@ -139,7 +134,6 @@ impl ProtocolConfig {
             name: protocol_name,
             max_request_size: 1024 * 1024,
             max_response_size: 16 * 1024 * 1024,
-            request_timeout: Duration::from_secs(20),
             inbound_queue: None,
         }
     }
@@ -319,9 +313,12 @@ struct RequestProcessingOutcome {
 impl RequestResponseFactoryBehaviour {
     /// Creates a new behaviour. Must be passed a list of supported protocols. Returns an error if
     /// the same protocol is passed twice.
+    ///
+    /// Outgoing requests of all protocols time out after `request_timeout`.
     pub fn new(
         list: impl IntoIterator<Item = Box<dyn RequestHandler>>,
         max_concurrent_streams: usize,
+        request_timeout: Duration,
     ) -> Result<Self, RegisterError> {
         let mut protocols = HashMap::new();
         let mut request_handlers = Vec::new();
@@ -341,7 +338,7 @@ impl RequestResponseFactoryBehaviour {
                 },
                 iter::once(StreamProtocol::new(config.name)).zip(iter::repeat(protocol_support)),
                 RequestResponseConfig::default()
-                    .with_request_timeout(config.request_timeout)
+                    .with_request_timeout(request_timeout)
                     .with_max_concurrent_streams(max_concurrent_streams),
             );
 
@@ -827,10 +824,11 @@ impl NetworkBehaviour for RequestResponseFactoryBehaviour {
                                 .remove(&(protocol.clone(), request_id).into())
                             {
                                 Some((started, pending_response)) => {
-                                    if pending_response
-                                        .send(Err(RequestFailure::Network(error)))
-                                        .is_err()
-                                    {
+                                    let failure = match error {
+                                        OutboundFailure::Timeout => RequestFailure::Timeout,
+                                        error => RequestFailure::Network(error),
+                                    };
+                                    if pending_response.send(Err(failure)).is_err() {
                                         debug!(
                                             %request_id,
                                             "Request failed. At the same time local node is no longer interested in \
@@ -913,6 +911,9 @@ pub enum RequestFailure {
     Refused,
     #[error("The remote replied, but the local node is no longer interested in the response.")]
     Obsolete,
+    /// Remote didn't answer within request timeout.
+    #[error("Remote didn't answer within request timeout.")]
+    Timeout,
     /// Problem on the network.
     #[error("Problem on the network: {0}")]
     Network(OutboundFailure),
@@ -41,7 +41,8 @@ async fn build_swarm(
         .into_iter()
         .map(|config| Box::new(MockRunner(config)) as Box<dyn RequestHandler>)
         .collect::<Vec<_>>();
-    let behaviour = RequestResponseFactoryBehaviour::new(configs, 100).unwrap();
+    let behaviour =
+        RequestResponseFactoryBehaviour::new(configs, 100, Duration::from_secs(30)).unwrap();
 
     let mut swarm = SwarmBuilder::with_new_identity()
         .with_tokio()
@@ -88,7 +89,6 @@ async fn basic_request_response_works() {
                 name: protocol_name,
                 max_request_size: 1024,
                 max_response_size: 1024 * 1024,
-                request_timeout: Duration::from_secs(30),
                 inbound_queue: Some(tx),
             };
 
@@ -164,7 +164,6 @@ async fn max_response_size_exceeded() {
                 name: protocol_name,
                 max_request_size: 1024,
                 max_response_size: 8, // <-- important for the test
-                request_timeout: Duration::from_secs(30),
                 inbound_queue: Some(tx),
             };
 
@@ -249,14 +248,12 @@ async fn request_id_collision() {
                 name: protocol_name_1,
                 max_request_size: 1024,
                 max_response_size: 1024 * 1024,
-                request_timeout: Duration::from_secs(30),
                 inbound_queue: None,
             },
             ProtocolConfig {
                 name: protocol_name_2,
                 max_request_size: 1024,
                 max_response_size: 1024 * 1024,
-                request_timeout: Duration::from_secs(30),
                 inbound_queue: None,
             },
         ];
@@ -273,14 +270,12 @@ async fn request_id_collision() {
                 name: protocol_name_1,
                 max_request_size: 1024,
                 max_response_size: 1024 * 1024,
-                request_timeout: Duration::from_secs(30),
                 inbound_queue: Some(tx_1),
             },
             ProtocolConfig {
                 name: protocol_name_2,
                 max_request_size: 1024,
                 max_response_size: 1024 * 1024,
-                request_timeout: Duration::from_secs(30),
                 inbound_queue: Some(tx_2),
             },
         ];
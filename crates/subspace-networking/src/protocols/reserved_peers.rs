@@ -11,10 +11,10 @@ use libp2p::core::{Endpoint, Multiaddr};
 use libp2p::swarm::behaviour::{ConnectionEstablished, FromSwarm};
 use libp2p::swarm::dial_opts::DialOpts;
 use libp2p::swarm::{
-    ConnectionClosed, ConnectionDenied, ConnectionId, DialFailure, NetworkBehaviour, THandler,
-    THandlerInEvent, THandlerOutEvent, ToSwarm,
+    ConnectionClosed, ConnectionDenied, ConnectionId, DialFailure, NetworkBehaviour, NotifyHandler,
+    THandler, THandlerInEvent, THandlerOutEvent, ToSwarm,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 use tracing::{debug, trace};
@@ -51,6 +51,8 @@ use crate::utils::strip_peer_id;
 /// in its `reserved peers set`, regular connection attempts will occur, but these connections will
 /// be dismissed on the other side due to the `KeepAlive` policy.
 ///
+/// Reserved peers can be added and removed at runtime, in which case `KeepAlive` policy of already
+/// established connections to those peers is updated accordingly.
 #[derive(Debug)]
 pub struct Behaviour {
     /// Protocol configuration.
@@ -58,6 +60,10 @@ pub struct Behaviour {
     /// A mapping from `PeerId` to `ReservedPeerState`, where each `ReservedPeerState`
     /// represents the current state of the connection to a reserved peer.
     reserved_peers_state: HashMap<PeerId, ReservedPeerState>,
+    /// Established connections of all peers, used to update handlers when reservation changes.
+    connections: HashMap<PeerId, HashSet<ConnectionId>>,
+    /// Pending reservation updates for connection handlers.
+    pending_handler_notifications: VecDeque<(PeerId, ConnectionId, bool)>,
     /// Delay between dialing attempts.
     dialing_delay: Delay,
     /// Future waker.
@@ -126,11 +132,70 @@ impl Behaviour {
         Self {
             config,
             reserved_peers_state,
+            connections: HashMap::new(),
+            pending_handler_notifications: VecDeque::new(),
             waker: None,
             dialing_delay,
         }
     }
 
+    /// Adds a reserved peer, it will be dialed on the next dialing attempt unless already
+    /// connected.
+    pub fn add_reserved_peer(&mut self, peer_id: PeerId, address: Multiaddr) {
+        debug!(%peer_id, %address, "Adding reserved peer.");
+
+        let connection_status = if self.connections.contains_key(&peer_id) {
+            ConnectionStatus::Connected
+        } else {
+            ConnectionStatus::NotConnected
+        };
+        self.reserved_peers_state.insert(
+            peer_id,
+            ReservedPeerState {
+                connection_status,
+                peer_id,
+                address,
+            },
+        );
+
+        self.notify_handlers(peer_id, true);
+    }
+
+    /// Removes a reserved peer, it will not be dialed anymore and its connections are allowed to
+    /// close.
+    ///
+    /// Returns `true` if the peer was reserved.
+    pub fn remove_reserved_peer(&mut self, peer_id: &PeerId) -> bool {
+        if self.reserved_peers_state.remove(peer_id).is_none() {
+            return false;
+        }
+
+        debug!(%peer_id, "Removed reserved peer.");
+
+        self.notify_handlers(*peer_id, false);
+
+        true
+    }
+
+    /// Reserved peers with their addresses.
+    pub fn reserved_peers(&self) -> impl Iterator<Item = (PeerId, &Multiaddr)> {
+        self.reserved_peers_state
+            .values()
+            .map(|state| (state.peer_id, &state.address))
+    }
+
+    fn notify_handlers(&mut self, peer_id: PeerId, connected_to_reserved_peer: bool) {
+        if let Some(connections) = self.connections.get(&peer_id) {
+            self.pending_handler_notifications.extend(
+                connections
+                    .iter()
+                    .map(|connection_id| (peer_id, *connection_id, connected_to_reserved_peer)),
+            );
+        }
+
+        self.wake();
+    }
+
     /// Create a connection handler for the reserved peers protocol.
     fn new_reserved_peers_handler(&self, peer_id: &PeerId) -> Handler {
         Handler::new(self.reserved_peers_state.contains_key(peer_id))
@@ -170,7 +235,16 @@ impl NetworkBehaviour for Behaviour {
 
     fn on_swarm_event(&mut self, event: FromSwarm) {
         match event {
-            FromSwarm::ConnectionEstablished(ConnectionEstablished { peer_id, .. }) => {
+            FromSwarm::ConnectionEstablished(ConnectionEstablished {
+                peer_id,
+                connection_id,
+                ..
+            }) => {
+                self.connections
+                    .entry(peer_id)
+                    .or_default()
+                    .insert(connection_id);
+
                 if let Some(state) = self.reserved_peers_state.get_mut(&peer_id) {
                     state.connection_status = ConnectionStatus::Connected;
 
@@ -180,9 +254,18 @@ impl NetworkBehaviour for Behaviour {
             }
             FromSwarm::ConnectionClosed(ConnectionClosed {
                 peer_id,
+                connection_id,
                 remaining_established,
                 ..
             }) => {
+                if let Some(connections) = self.connections.get_mut(&peer_id) {
+                    connections.remove(&connection_id);
+
+                    if connections.is_empty() {
+                        self.connections.remove(&peer_id);
+                    }
+                }
+
                 if let Some(state) = self.reserved_peers_state.get_mut(&peer_id)
                     && remaining_established == 0
                 {
@@ -221,6 +304,16 @@ impl NetworkBehaviour for Behaviour {
         &mut self,
         cx: &mut Context<'_>,
     ) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+        if let Some((peer_id, connection_id, connected_to_reserved_peer)) =
+            self.pending_handler_notifications.pop_front()
+        {
+            return Poll::Ready(ToSwarm::NotifyHandler {
+                peer_id,
+                handler: NotifyHandler::One(connection_id),
+                event: connected_to_reserved_peer,
+            });
+        }
+
         // Schedule new peer dialing.
         match self.dialing_delay.poll_unpin(cx) {
             Poll::Pending => {}
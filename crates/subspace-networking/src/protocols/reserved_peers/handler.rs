@@ -2,7 +2,6 @@ use libp2p::core::upgrade::DeniedUpgrade;
 use libp2p::swarm::handler::ConnectionEvent;
 use libp2p::swarm::{ConnectionHandler, ConnectionHandlerEvent, SubstreamProtocol};
 use std::task::{Context, Poll};
-use void::Void;
 
 /// Connection handler for managing connections within our `reserved peers` protocol.
 ///
@@ -16,7 +15,8 @@ use void::Void;
 /// connection to close (`KeepAlive::No`).
 ///
 /// This behavior ensures that connections to reserved peers are maintained persistently,
-/// while connections to non-reserved peers are allowed to close.
+/// while connections to non-reserved peers are allowed to close. The behaviour notifies the
+/// handler when the peer is added to or removed from the reserved peers at runtime.
 pub struct Handler {
     /// A boolean flag indicating whether the handler is currently connected to a reserved peer.
     connected_to_reserved_peer: bool,
@@ -32,7 +32,7 @@ impl Handler {
 }
 
 impl ConnectionHandler for Handler {
-    type FromBehaviour = Void;
+    type FromBehaviour = bool;
     type ToBehaviour = ();
     type InboundProtocol = DeniedUpgrade;
     type OutboundProtocol = DeniedUpgrade;
@@ -43,7 +43,9 @@ impl ConnectionHandler for Handler {
         SubstreamProtocol::new(DeniedUpgrade, ())
     }
 
-    fn on_behaviour_event(&mut self, _: Void) {}
+    fn on_behaviour_event(&mut self, connected_to_reserved_peer: bool) {
+        self.connected_to_reserved_peer = connected_to_reserved_peer;
+    }
 
     fn connection_keep_alive(&self) -> bool {
         self.connected_to_reserved_peer
@@ -187,6 +187,88 @@ async fn test_reserved_peers_dial_event() {
     // We've received the reserved peer dialing event.
 }
 
+#[tokio::test]
+async fn test_reserved_peer_added_at_runtime_is_dialed() {
+    let connection_timeout = Duration::from_millis(1300);
+    let long_delay = Duration::from_millis(2000);
+
+    let identity1 = Keypair::generate_ed25519();
+    let identity2 = Keypair::generate_ed25519();
+
+    let peer2_id = identity2.public().to_peer_id();
+
+    let mut peer1 = new_ephemeral(
+        identity1,
+        connection_timeout,
+        Behaviour::new(Config {
+            reserved_peers: Vec::new(),
+            dialing_interval: DIALING_INTERVAL_IN_SECS,
+        }),
+    );
+
+    peer1.listen().with_memory_addr_external().await;
+    peer1
+        .behaviour_mut()
+        .add_reserved_peer(peer2_id, "/memory/0".parse().unwrap());
+    assert_eq!(peer1.behaviour().reserved_peers().count(), 1);
+
+    let dialed = async {
+        loop {
+            if let SwarmEvent::Dialing { peer_id, .. } = peer1.next_swarm_event().await
+                && peer_id == Some(peer2_id)
+            {
+                break;
+            }
+        }
+    };
+
+    select! {
+        _ = dialed.fuse() => {},
+        _ = sleep(long_delay).fuse() => {
+            panic!("No reserved peers dialing.");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_removed_reserved_peer_is_not_dialed() {
+    let connection_timeout = Duration::from_millis(1300);
+    let long_delay = Duration::from_millis(2000);
+
+    let identity1 = Keypair::generate_ed25519();
+    let identity2 = Keypair::generate_ed25519();
+
+    let peer2_id = identity2.public().to_peer_id();
+    let peer2_address = format!("/memory/0/p2p/{peer2_id}");
+
+    let mut peer1 = new_ephemeral(
+        identity1,
+        connection_timeout,
+        Behaviour::new(Config {
+            reserved_peers: vec![peer2_address.parse().unwrap()],
+            dialing_interval: DIALING_INTERVAL_IN_SECS,
+        }),
+    );
+
+    peer1.listen().with_memory_addr_external().await;
+    assert!(peer1.behaviour_mut().remove_reserved_peer(&peer2_id));
+    assert!(!peer1.behaviour_mut().remove_reserved_peer(&peer2_id));
+    assert_eq!(peer1.behaviour().reserved_peers().count(), 0);
+
+    loop {
+        select! {
+            event = peer1.next_swarm_event().fuse() => {
+                if let SwarmEvent::Dialing { peer_id, .. } = event {
+                    assert_ne!(peer_id, Some(peer2_id), "Removed reserved peer was dialed");
+                }
+            },
+            _ = sleep(long_delay).fuse() => {
+                break;
+            }
+        }
+    }
+}
+
 fn new_ephemeral<NB: NetworkBehaviour>(
     identity: Keypair,
     connection_timeout: Duration,
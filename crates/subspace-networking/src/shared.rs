@@ -2,19 +2,55 @@
 //! queries, subscriptions, various events and shared information.
 
 use crate::protocols::request_response::request_response_factory::RequestFailure;
-use crate::utils::Handler;
 use crate::utils::multihash::Multihash;
+use crate::utils::peer_reputation::ReputationChange;
 use crate::utils::rate_limiter::RateLimiter;
+use crate::utils::{BandwidthCounters, Handler};
 use bytes::Bytes;
 use futures::channel::{mpsc, oneshot};
+use libp2p::autonat::NatStatus;
 use libp2p::gossipsub::{PublishError, Sha256Topic, SubscriptionError};
 use libp2p::kad::{PeerRecord, RecordKey};
 use libp2p::{Multiaddr, PeerId};
 use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
 use tokio::sync::OwnedSemaphorePermit;
 
+/// Reachability of bootstrap nodes observed since the node was started.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct BootstrapStatus {
+    /// Number of bootstrap nodes that were connected to successfully.
+    pub reachable: usize,
+    /// Number of bootstrap nodes that could not be connected to.
+    pub unreachable: usize,
+}
+
+/// Total number of bytes transferred by the node over all connections since it was started.
+///
+/// Includes transport overhead (encryption, multiplexing) and isn't broken down by protocol.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct BandwidthStats {
+    /// Number of bytes received.
+    pub inbound_bytes: u64,
+    /// Number of bytes sent.
+    pub outbound_bytes: u64,
+}
+
+/// Point-in-time snapshot of the node state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeStatus {
+    /// Number of connected peers.
+    pub connected_peers: usize,
+    /// Number of connected reserved peers.
+    pub connected_reserved_peers: usize,
+    /// Number of peers in Kademlia routing table.
+    pub routing_table_size: usize,
+    /// Addresses node is listening on.
+    pub listeners: Vec<Multiaddr>,
+}
+
 /// Represents Kademlia events (RoutablePeer, PendingRoutablePeer, UnroutablePeer).
 #[derive(Clone, Debug)]
 pub enum PeerDiscovered {
@@ -102,9 +138,30 @@ pub(crate) enum Command {
     BanPeer {
         peer_id: PeerId,
     },
+    ReportPeer {
+        peer_id: PeerId,
+        change: ReputationChange,
+    },
+    BannedPeers {
+        result_sender: oneshot::Sender<Vec<PeerId>>,
+    },
     Dial {
         address: Multiaddr,
     },
+    AddReservedPeer {
+        peer_id: PeerId,
+        address: Multiaddr,
+    },
+    RemoveReservedPeer {
+        peer_id: PeerId,
+    },
+    ReservedPeers {
+        result_sender: oneshot::Sender<Vec<Multiaddr>>,
+    },
+    Status {
+        result_sender: oneshot::Sender<NodeStatus>,
+    },
+    Shutdown,
     ConnectedPeers {
         result_sender: oneshot::Sender<Vec<PeerId>>,
     },
@@ -134,6 +191,11 @@ pub(crate) struct Shared {
     pub(crate) listeners: Mutex<Vec<Multiaddr>>,
     pub(crate) external_addresses: Mutex<Vec<Multiaddr>>,
     pub(crate) num_established_peer_connections: Arc<AtomicUsize>,
+    /// Whether each of the dialed bootstrap nodes was reachable.
+    pub(crate) bootstrap_nodes_reachability: Mutex<HashMap<PeerId, bool>>,
+    /// NAT status detected by AutoNAT.
+    pub(crate) nat_status: Mutex<NatStatus>,
+    pub(crate) bandwidth_counters: BandwidthCounters,
     /// Sender end of the channel for sending commands to the swarm.
     pub(crate) command_sender: mpsc::Sender<Command>,
     pub(crate) rate_limiter: RateLimiter,
@@ -144,6 +206,7 @@ impl Shared {
         id: PeerId,
         command_sender: mpsc::Sender<Command>,
         rate_limiter: RateLimiter,
+        bandwidth_counters: BandwidthCounters,
     ) -> Self {
         Self {
             handlers: Handlers::default(),
@@ -151,6 +214,9 @@ impl Shared {
             listeners: Mutex::default(),
             external_addresses: Mutex::default(),
             num_established_peer_connections: Arc::new(AtomicUsize::new(0)),
+            bootstrap_nodes_reachability: Mutex::default(),
+            nat_status: Mutex::new(NatStatus::Unknown),
+            bandwidth_counters,
             command_sender,
             rate_limiter,
         }
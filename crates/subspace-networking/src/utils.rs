@@ -2,12 +2,16 @@
 
 pub(crate) mod key_with_distance;
 pub mod multihash;
+pub mod peer_rate_limiter;
+pub mod peer_reputation;
 pub mod piece_provider;
 pub(crate) mod rate_limiter;
 
 use event_listener_primitives::Bag;
 use libp2p::multiaddr::Protocol;
 use libp2p::{Multiaddr, PeerId};
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
 use prometheus_client::metrics::gauge::Gauge;
 use prometheus_client::registry::Registry;
 use std::sync::Arc;
@@ -15,9 +19,19 @@ use tracing::warn;
 
 const NETWORKING_REGISTRY_PREFIX: &str = "subspace";
 
+/// Total number of bytes transferred over all connections, including protocol overhead
+#[derive(Debug, Default, Clone)]
+pub(crate) struct BandwidthCounters {
+    pub(crate) inbound: Counter,
+    pub(crate) outbound: Counter,
+}
+
 /// Metrics for Subspace networking
 pub struct SubspaceMetrics {
     established_connections: Gauge,
+    inbound_requests: Family<Vec<(&'static str, String)>, Counter>,
+    inbound_request_failures: Family<Vec<(&'static str, String)>, Counter>,
+    bandwidth: BandwidthCounters,
 }
 
 impl SubspaceMetrics {
@@ -32,11 +46,44 @@ impl SubspaceMetrics {
             gauge.clone(),
         );
 
+        let inbound_requests = Family::default();
+        sub_registry.register(
+            "inbound_requests",
+            "Inbound request-response requests answered, by protocol",
+            inbound_requests.clone(),
+        );
+
+        let inbound_request_failures = Family::default();
+        sub_registry.register(
+            "inbound_request_failures",
+            "Inbound request-response requests that failed to be answered, by protocol",
+            inbound_request_failures.clone(),
+        );
+
+        let bandwidth = BandwidthCounters::default();
+        sub_registry.register(
+            "inbound_bytes",
+            "Bytes received over all connections",
+            bandwidth.inbound.clone(),
+        );
+        sub_registry.register(
+            "outbound_bytes",
+            "Bytes sent over all connections",
+            bandwidth.outbound.clone(),
+        );
+
         Self {
             established_connections: gauge,
+            inbound_requests,
+            inbound_request_failures,
+            bandwidth,
         }
     }
 
+    pub(crate) fn bandwidth_counters(&self) -> BandwidthCounters {
+        self.bandwidth.clone()
+    }
+
     pub(crate) fn inc_established_connections(&self) {
         self.established_connections.inc();
     }
@@ -44,6 +91,18 @@ impl SubspaceMetrics {
     pub(crate) fn dec_established_connections(&self) {
         self.established_connections.dec();
     }
+
+    pub(crate) fn inc_inbound_requests(&self, protocol: &str, success: bool) {
+        let counter = if success {
+            &self.inbound_requests
+        } else {
+            &self.inbound_request_failures
+        };
+
+        counter
+            .get_or_create(&vec![("protocol", protocol.to_string())])
+            .inc();
+    }
 }
 
 /// This test is successful only for global IP addresses and DNS names.
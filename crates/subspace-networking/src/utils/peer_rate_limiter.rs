@@ -0,0 +1,69 @@
+//! Per-peer rate limiting for incoming requests.
+
+#[cfg(test)]
+mod tests;
+
+use libp2p::PeerId;
+use parking_lot::Mutex;
+use schnellru::{ByLength, LruMap};
+use std::num::NonZeroU32;
+use std::time::Instant;
+
+/// Token bucket of a single peer
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter that tracks a bounded number of most recently seen peers.
+///
+/// Each peer can make up to `burst` requests at once, after which requests are allowed at
+/// `rate_per_second`. Peers evicted from the LRU start with a full bucket again.
+#[derive(Debug)]
+pub struct PeerRateLimiter {
+    rate_per_second: f64,
+    burst: f64,
+    buckets: Mutex<LruMap<PeerId, TokenBucket>>,
+}
+
+impl PeerRateLimiter {
+    /// Create new rate limiter that keeps state for at most `max_peers` peers
+    pub fn new(rate_per_second: NonZeroU32, burst: NonZeroU32, max_peers: u32) -> Self {
+        Self {
+            rate_per_second: f64::from(rate_per_second.get()),
+            burst: f64::from(burst.get()),
+            buckets: Mutex::new(LruMap::new(ByLength::new(max_peers))),
+        }
+    }
+
+    /// Try to consume a token for a request from `peer_id`.
+    ///
+    /// Returns `false` if peer exceeded its rate limit and request should be rejected.
+    pub fn try_acquire(&self, peer_id: PeerId) -> bool {
+        self.try_acquire_at(peer_id, Instant::now())
+    }
+
+    fn try_acquire_at(&self, peer_id: PeerId, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock();
+        let Some(bucket) = buckets.get_or_insert(peer_id, || TokenBucket {
+            tokens: self.burst,
+            last_refill: now,
+        }) else {
+            // Zero capacity, nothing to track
+            return true;
+        };
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * self.rate_per_second).min(self.burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
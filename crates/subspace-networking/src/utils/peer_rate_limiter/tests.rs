@@ -0,0 +1,49 @@
+use crate::utils::peer_rate_limiter::PeerRateLimiter;
+use libp2p::PeerId;
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+#[test]
+fn burst_is_throttled() {
+    let rate_limiter =
+        PeerRateLimiter::new(NonZeroU32::new(2).unwrap(), NonZeroU32::new(5).unwrap(), 10);
+    let peer_id = PeerId::random();
+    let other_peer_id = PeerId::random();
+    let now = Instant::now();
+
+    for _ in 0..5 {
+        assert!(rate_limiter.try_acquire_at(peer_id, now));
+    }
+    assert!(!rate_limiter.try_acquire_at(peer_id, now));
+
+    // Other peers are not affected
+    assert!(rate_limiter.try_acquire_at(other_peer_id, now));
+
+    // Tokens are refilled over time, but never above the burst size
+    let later = now + Duration::from_millis(500);
+    assert!(rate_limiter.try_acquire_at(peer_id, later));
+    assert!(!rate_limiter.try_acquire_at(peer_id, later));
+
+    let much_later = later + Duration::from_secs(60);
+    for _ in 0..5 {
+        assert!(rate_limiter.try_acquire_at(peer_id, much_later));
+    }
+    assert!(!rate_limiter.try_acquire_at(peer_id, much_later));
+}
+
+#[test]
+fn tracked_peers_are_bounded() {
+    let rate_limiter =
+        PeerRateLimiter::new(NonZeroU32::new(1).unwrap(), NonZeroU32::new(1).unwrap(), 1);
+    let peer_id = PeerId::random();
+    let other_peer_id = PeerId::random();
+    let now = Instant::now();
+
+    assert!(rate_limiter.try_acquire_at(peer_id, now));
+    assert!(!rate_limiter.try_acquire_at(peer_id, now));
+
+    // Tracking another peer evicts the first one
+    assert!(rate_limiter.try_acquire_at(other_peer_id, now));
+    assert_eq!(rate_limiter.buckets.lock().len(), 1);
+    assert!(rate_limiter.try_acquire_at(peer_id, now));
+}
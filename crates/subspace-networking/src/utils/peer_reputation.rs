@@ -0,0 +1,145 @@
+//! Lightweight peer reputation tracking with temporary bans of misbehaving peers.
+
+#[cfg(test)]
+mod tests;
+
+use libp2p::PeerId;
+use schnellru::{ByLength, LruMap};
+use std::time::{Duration, Instant};
+
+/// Change of peer reputation reported for a specific reason.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ReputationChange {
+    /// Reputation delta, negative values penalize the peer.
+    pub value: i32,
+    /// Reason for reputation change, used for logging.
+    pub reason: &'static str,
+}
+
+impl ReputationChange {
+    /// Peer sent data that failed verification (invalid piece, segment header, etc.).
+    pub const INVALID_DATA: Self = Self::new(-100, "Invalid data");
+    /// Peer violated request-response protocol (malformed request, request flood, etc.).
+    pub const PROTOCOL_VIOLATION: Self = Self::new(-50, "Protocol violation");
+
+    /// Create new reputation change.
+    pub const fn new(value: i32, reason: &'static str) -> Self {
+        Self { value, reason }
+    }
+}
+
+/// Outcome of the reputation change of a peer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ReportOutcome {
+    /// Reputation was updated, peer is not banned.
+    Updated(i32),
+    /// Reputation dropped below the threshold and peer got banned.
+    ///
+    /// If the maximum number of bans was reached, the oldest ban is lifted early to make space for
+    /// the new one, such peer needs to be unblocked by the caller.
+    Banned { evicted_ban: Option<PeerId> },
+    /// Peer is already banned, reputation is not tracked until the ban expires.
+    AlreadyBanned,
+}
+
+/// Tracks reputation of a bounded number of most recently reported peers.
+///
+/// Reputation starts at zero and can't go above it, so positive changes only restore reputation
+/// lost earlier. Peers with reputation at or below the ban threshold are banned for configured
+/// duration, after which they start with a clean reputation again. The number of bans is bounded
+/// by the same capacity as reputations.
+#[derive(Debug)]
+pub(crate) struct PeerReputations {
+    ban_threshold: i32,
+    ban_duration: Duration,
+    max_bans: u32,
+    reputations: LruMap<PeerId, i32>,
+    /// Banned peers with time of ban expiration, oldest bans are evicted first
+    bans: LruMap<PeerId, Instant>,
+}
+
+impl PeerReputations {
+    pub(crate) fn new(ban_threshold: i32, ban_duration: Duration, capacity: u32) -> Self {
+        Self {
+            ban_threshold,
+            ban_duration,
+            max_bans: capacity,
+            reputations: LruMap::new(ByLength::new(capacity)),
+            bans: LruMap::new(ByLength::new(capacity)),
+        }
+    }
+
+    /// Apply reputation change to the peer
+    pub(crate) fn report(&mut self, peer_id: PeerId, change: ReputationChange) -> ReportOutcome {
+        self.report_at(peer_id, change, Instant::now())
+    }
+
+    fn report_at(
+        &mut self,
+        peer_id: PeerId,
+        change: ReputationChange,
+        now: Instant,
+    ) -> ReportOutcome {
+        if self.bans.peek(&peer_id).is_some() {
+            return ReportOutcome::AlreadyBanned;
+        }
+
+        let reputation = self
+            .reputations
+            .peek(&peer_id)
+            .copied()
+            .unwrap_or_default()
+            .saturating_add(change.value)
+            .min(0);
+
+        if reputation <= self.ban_threshold {
+            self.reputations.remove(&peer_id);
+            // Evict explicitly rather than letting the map do it, the caller needs to know about it
+            let evicted_ban = if self.bans.len() >= self.max_bans as usize {
+                self.bans.pop_oldest().map(|(peer_id, _expires_at)| peer_id)
+            } else {
+                None
+            };
+            self.bans.insert(peer_id, now + self.ban_duration);
+            return ReportOutcome::Banned { evicted_ban };
+        }
+
+        if reputation == 0 {
+            self.reputations.remove(&peer_id);
+        } else {
+            self.reputations.insert(peer_id, reputation);
+        }
+
+        ReportOutcome::Updated(reputation)
+    }
+
+    /// Whether peer is currently banned due to low reputation
+    pub(crate) fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.bans.peek(peer_id).is_some()
+    }
+
+    /// Forget everything about the peer, returns `true` if peer was banned.
+    pub(crate) fn remove(&mut self, peer_id: &PeerId) -> bool {
+        self.reputations.remove(peer_id);
+        self.bans.remove(peer_id).is_some()
+    }
+
+    /// Remove expired bans and return peers whose bans have expired
+    pub(crate) fn remove_expired_bans(&mut self) -> Vec<PeerId> {
+        self.remove_expired_bans_at(Instant::now())
+    }
+
+    fn remove_expired_bans_at(&mut self, now: Instant) -> Vec<PeerId> {
+        let mut expired = Vec::new();
+        self.bans.retain(|peer_id, expires_at| {
+            if *expires_at <= now {
+                expired.push(*peer_id);
+                false
+            } else {
+                true
+            }
+        });
+
+        expired
+    }
+}
@@ -0,0 +1,103 @@
+use crate::utils::peer_reputation::{PeerReputations, ReportOutcome, ReputationChange};
+use libp2p::PeerId;
+use std::time::{Duration, Instant};
+
+#[test]
+fn ban_after_enough_violations() {
+    let mut reputations = PeerReputations::new(-250, Duration::from_secs(60), 10);
+    let peer_id = PeerId::random();
+    let other_peer_id = PeerId::random();
+    let now = Instant::now();
+
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::INVALID_DATA, now),
+        ReportOutcome::Updated(-100)
+    );
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::INVALID_DATA, now),
+        ReportOutcome::Updated(-200)
+    );
+    assert!(!reputations.is_banned(&peer_id));
+
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::PROTOCOL_VIOLATION, now),
+        ReportOutcome::Banned { evicted_ban: None }
+    );
+    assert!(reputations.is_banned(&peer_id));
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::INVALID_DATA, now),
+        ReportOutcome::AlreadyBanned
+    );
+
+    // Other peers are not affected
+    assert!(!reputations.is_banned(&other_peer_id));
+
+    // Ban expires after configured duration and peer starts with clean reputation
+    assert!(
+        reputations
+            .remove_expired_bans_at(now + Duration::from_secs(59))
+            .is_empty()
+    );
+    assert_eq!(
+        reputations.remove_expired_bans_at(now + Duration::from_secs(60)),
+        [peer_id]
+    );
+    assert!(!reputations.is_banned(&peer_id));
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::PROTOCOL_VIOLATION, now),
+        ReportOutcome::Updated(-50)
+    );
+}
+
+#[test]
+fn reputation_does_not_go_above_zero() {
+    let mut reputations = PeerReputations::new(-100, Duration::from_secs(60), 10);
+    let peer_id = PeerId::random();
+    let now = Instant::now();
+
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::new(1_000, "Good behavior"), now),
+        ReportOutcome::Updated(0)
+    );
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::PROTOCOL_VIOLATION, now),
+        ReportOutcome::Updated(-50)
+    );
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::new(20, "Good behavior"), now),
+        ReportOutcome::Updated(-30)
+    );
+
+    assert!(!reputations.remove(&peer_id));
+    assert_eq!(
+        reputations.report_at(peer_id, ReputationChange::PROTOCOL_VIOLATION, now),
+        ReportOutcome::Updated(-50)
+    );
+}
+
+#[test]
+fn number_of_bans_is_bounded() {
+    let mut reputations = PeerReputations::new(-100, Duration::from_secs(60), 2);
+    let peer_ids = [PeerId::random(), PeerId::random(), PeerId::random()];
+    let now = Instant::now();
+
+    assert_eq!(
+        reputations.report_at(peer_ids[0], ReputationChange::INVALID_DATA, now),
+        ReportOutcome::Banned { evicted_ban: None }
+    );
+    assert_eq!(
+        reputations.report_at(peer_ids[1], ReputationChange::INVALID_DATA, now),
+        ReportOutcome::Banned { evicted_ban: None }
+    );
+
+    // The oldest ban is evicted to make space for the new one
+    assert_eq!(
+        reputations.report_at(peer_ids[2], ReputationChange::INVALID_DATA, now),
+        ReportOutcome::Banned {
+            evicted_ban: Some(peer_ids[0])
+        }
+    );
+    assert!(!reputations.is_banned(&peer_ids[0]));
+    assert!(reputations.is_banned(&peer_ids[1]));
+    assert!(reputations.is_banned(&peer_ids[2]));
+}
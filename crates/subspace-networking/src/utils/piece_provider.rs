@@ -1,5 +1,8 @@
 //! Provides methods to retrieve pieces from DSN.
 
+#[cfg(test)]
+mod tests;
+
 use crate::constructor::DummyRecordStore;
 use crate::protocols::request_response::handlers::cached_piece_by_index::{
     CachedPieceByIndexRequest, CachedPieceByIndexResponse, PieceResult,
@@ -7,6 +10,9 @@ use crate::protocols::request_response::handlers::cached_piece_by_index::{
 use crate::protocols::request_response::handlers::piece_by_index::{
     PieceByIndexRequest, PieceByIndexResponse,
 };
+use crate::protocols::request_response::handlers::piece_range::{
+    PieceRangeRequest, PieceRangeResponse,
+};
 use crate::utils::multihash::ToMultihash;
 use crate::{Multihash, Node};
 use async_lock::{Semaphore, SemaphoreGuard};
@@ -53,6 +59,15 @@ impl PieceValidator for NoPieceValidator {
     }
 }
 
+/// Result of downloading a range of pieces from a peer.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SyncedPieceRange {
+    /// Downloaded and validated pieces, in ascending order
+    pub pieces: Vec<(PieceIndex, Piece)>,
+    /// Pieces that peer didn't have, failed validation or couldn't be downloaded
+    pub missing: Vec<PieceIndex>,
+}
+
 /// Piece provider with cancellation and piece validator.
 /// Use `NoPieceValidator` to disable validation.
 #[derive(Clone)]
@@ -228,6 +243,88 @@ where
         None
     }
 
+    /// Get contiguous range of `count` pieces starting at `start_index` from a particular peer.
+    ///
+    /// Pieces are requested in batches of up to [`PieceRangeRequest::MAX_PIECES`] pieces, pieces
+    /// that weren't downloaded for any reason are returned as missing.
+    ///
+    /// Local node must have [`PieceRangeRequestHandler`] registered in its request-response
+    /// protocols, otherwise requests fail with unknown protocol error and all pieces are returned
+    /// as missing.
+    ///
+    /// [`PieceRangeRequestHandler`]: crate::protocols::request_response::handlers::piece_range::PieceRangeRequestHandler
+    pub async fn sync_piece_range(
+        &self,
+        peer_id: PeerId,
+        start_index: PieceIndex,
+        count: u64,
+    ) -> SyncedPieceRange {
+        let mut synced_piece_range = SyncedPieceRange::default();
+        let end_index = u64::from(start_index).saturating_add(count);
+        let mut batch_start_index = u64::from(start_index);
+
+        while batch_start_index < end_index {
+            let request = PieceRangeRequest {
+                start_index: PieceIndex::new(batch_start_index),
+                count: (end_index - batch_start_index).min(u64::from(PieceRangeRequest::MAX_PIECES))
+                    as u32,
+            };
+            batch_start_index += u64::from(request.count);
+
+            let mut pieces = match self
+                .node
+                .send_generic_request(peer_id, Vec::new(), request.clone())
+                .await
+            {
+                Ok(PieceRangeResponse { pieces, missing }) => {
+                    trace!(
+                        %peer_id,
+                        start_index = %request.start_index,
+                        count = %request.count,
+                        missing = %missing.len(),
+                        "Piece range request succeeded"
+                    );
+
+                    pieces.into_iter().collect::<HashMap<_, _>>()
+                }
+                Err(error) => {
+                    debug!(
+                        %peer_id,
+                        start_index = %request.start_index,
+                        count = %request.count,
+                        ?error,
+                        "Piece range request failed"
+                    );
+
+                    HashMap::new()
+                }
+            };
+
+            // Only pieces from requested range are accepted, everything else is ignored
+            for piece_index in request.piece_indexes() {
+                let maybe_piece = match pieces.remove(&piece_index) {
+                    Some(piece) => {
+                        self.piece_validator
+                            .validate_piece(peer_id, piece_index, piece)
+                            .await
+                    }
+                    None => None,
+                };
+
+                match maybe_piece {
+                    Some(piece) => {
+                        synced_piece_range.pieces.push((piece_index, piece));
+                    }
+                    None => {
+                        synced_piece_range.missing.push(piece_index);
+                    }
+                }
+            }
+        }
+
+        synced_piece_range
+    }
+
     /// Get piece from archival storage (L1). The algorithm tries to get a piece from currently
     /// connected peers and falls back to random walking.
     pub async fn get_piece_from_archival_storage(
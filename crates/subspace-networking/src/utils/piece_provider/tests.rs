@@ -0,0 +1,103 @@
+use crate::node::tests::start_two_nodes;
+use crate::protocols::request_response::handlers::piece_range::{
+    PieceRangeRequest, PieceRangeRequestHandler, PieceRangeResponse,
+};
+use crate::utils::piece_provider::{NoPieceValidator, PieceProvider};
+use crate::{Config, NodeEvent};
+use async_lock::Semaphore;
+use futures::StreamExt;
+use libp2p::multiaddr::Protocol;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use subspace_core_primitives::pieces::{Piece, PieceIndex};
+use subspace_process::init_logger;
+
+#[tokio::test]
+async fn sync_piece_range_with_gaps() {
+    init_logger();
+
+    // Node 1 has 40 out of the first 50 pieces, every fifth piece is missing
+    let pieces = Arc::new(
+        (0..50)
+            .filter(|index| index % 5 != 0)
+            .map(|index| (PieceIndex::new(index), Piece::default()))
+            .collect::<HashMap<_, _>>(),
+    );
+    assert_eq!(pieces.len(), 40);
+
+    let config_1 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![PieceRangeRequestHandler::create(
+            move |_, request: PieceRangeRequest| {
+                let pieces = Arc::clone(&pieces);
+
+                async move {
+                    let mut response = PieceRangeResponse {
+                        pieces: Vec::new(),
+                        missing: Vec::new(),
+                    };
+
+                    for piece_index in request.piece_indexes() {
+                        match pieces.get(&piece_index) {
+                            Some(piece) => {
+                                response.pieces.push((piece_index, piece.clone()));
+                            }
+                            None => {
+                                response.missing.push(piece_index);
+                            }
+                        }
+                    }
+
+                    Some(response)
+                }
+            },
+        )],
+        ..Config::default()
+    };
+
+    let config_2 = Config {
+        listen_on: vec!["/ip4/0.0.0.0/tcp/0".parse().unwrap()],
+        allow_non_global_addresses_in_dht: true,
+        request_response_protocols: vec![PieceRangeRequestHandler::create(|_, _| async { None })],
+        ..Config::default()
+    };
+
+    let (node_1, node_1_addr, node_2) = start_two_nodes(config_1, |_, _| config_2).await;
+    let mut node_2_events = node_2.event_stream();
+
+    node_2
+        .dial(node_1_addr.with(Protocol::P2p(node_1.id())))
+        .await
+        .unwrap();
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let NodeEvent::PeerConnected(peer_id) = node_2_events.next().await.unwrap()
+                && peer_id == node_1.id()
+            {
+                break;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    let piece_provider = PieceProvider::new(node_2, NoPieceValidator, Arc::new(Semaphore::new(1)));
+    let synced_piece_range = piece_provider
+        .sync_piece_range(node_1.id(), PieceIndex::ZERO, 50)
+        .await;
+
+    assert_eq!(synced_piece_range.pieces.len(), 40);
+    assert_eq!(
+        synced_piece_range.missing,
+        (0..50).step_by(5).map(PieceIndex::new).collect::<Vec<_>>()
+    );
+    assert!(
+        synced_piece_range
+            .pieces
+            .iter()
+            .all(|(piece_index, _piece)| u64::from(*piece_index) % 5 != 0)
+    );
+}
@@ -12,7 +12,7 @@ use domain_service::config::{
     SubstrateConfiguration, SubstrateNetworkConfiguration, SubstrateRpcConfiguration,
 };
 use domain_service::providers::DefaultProvider;
-use domain_service::{FullBackend, FullClient};
+use domain_service::{FullBackend, FullClient, TransactionPoolLimits};
 use evm_domain_runtime::AccountId as AccountId20;
 use futures::StreamExt;
 use sc_chain_spec::{ChainType, GenericChainSpec, NoExtension, Properties};
@@ -36,6 +36,7 @@ use sp_domains::{DomainId, DomainInstanceData, DomainsApi, OperatorId, RuntimeTy
 use sp_runtime::traits::Block as BlockT;
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::Arc;
 use subspace_runtime::RuntimeApi as CRuntimeApi;
 use subspace_runtime_primitives::opaque::Block as CBlock;
@@ -109,6 +110,10 @@ pub(super) struct DomainOptions {
     #[clap(flatten)]
     rpc_options: RpcOptions,
 
+    /// Maximum RPC request payload size in MB, 15 MB by default.
+    #[arg(long, value_name = "MEGABYTES")]
+    rpc_max_request_size: Option<u32>,
+
     /// IP and port (TCP) to start Prometheus exporter on
     // TODO: Use the same registry as consensus chain instead
     #[clap(long)]
@@ -146,6 +151,13 @@ pub(super) struct DomainOptions {
     #[clap(flatten)]
     pub trie_cache_params: TrieCacheParams,
 
+    /// Path to directory with WASM runtimes that override on-chain domain runtimes.
+    ///
+    /// Runtimes are matched with on-chain runtimes by spec name and spec version embedded in the
+    /// WASM blob, file names don't matter.
+    #[arg(long, value_name = "PATH")]
+    wasm_runtime_overrides: Option<PathBuf>,
+
     /// Domain type specific arguments.
     ///
     /// The command-line arguments provided first will be passed to the embedded consensus node,
@@ -191,6 +203,7 @@ pub(super) struct DomainConfiguration {
     pub(super) domain_id: DomainId,
     pub(super) operator_id: Option<OperatorId>,
     pub(super) domain_type_args: Vec<String>,
+    pub(super) transaction_pool_limits: TransactionPoolLimits,
 }
 
 #[expect(clippy::result_large_err, reason = "Comes from Substrate")]
@@ -203,6 +216,7 @@ pub(super) fn create_domain_configuration(
         domain_id: maybe_domain_id,
         mut operator_id,
         rpc_options,
+        rpc_max_request_size,
         prometheus_listen_on,
         pruning_params,
         network_options,
@@ -211,11 +225,13 @@ pub(super) fn create_domain_configuration(
         pool_config,
         runtime_params,
         trie_cache_params,
+        wasm_runtime_overrides,
         domain_type_args,
     } = domain_options;
 
     let domain_id;
     let transaction_pool;
+    let transaction_pool_limits;
     let rpc_cors;
     // Development mode handling is limited to this section
     {
@@ -241,6 +257,8 @@ pub(super) fn create_domain_configuration(
             }
         };
         transaction_pool = pool_config.transaction_pool(dev);
+        transaction_pool_limits =
+            TransactionPoolLimits::new(pool_config.pool_limit, pool_config.pool_kbytes * 1024);
         rpc_cors = rpc_options.rpc_cors.unwrap_or_else(|| {
             if dev {
                 warn!("Running in --dev mode, RPC CORS has been disabled.");
@@ -425,6 +443,7 @@ pub(super) fn create_domain_configuration(
                 .rpc_message_buffer_capacity_per_connection,
             disable_batch_requests: rpc_options.rpc_disable_batch_requests,
             max_batch_request_len: rpc_options.rpc_max_batch_request_len,
+            max_request_size: rpc_max_request_size,
         },
         prometheus_listen_on,
         telemetry_endpoints: consensus_chain_configuration.telemetry_endpoints.clone(),
@@ -436,6 +455,7 @@ pub(super) fn create_domain_configuration(
             default_heap_pages: None,
             runtime_cache_size: runtime_params.runtime_cache_size,
         },
+        wasm_runtime_overrides,
         trie_cache_size: trie_cache_params.trie_cache_maximum_size(),
     };
 
@@ -444,6 +464,7 @@ pub(super) fn create_domain_configuration(
         domain_id,
         operator_id,
         domain_type_args,
+        transaction_pool_limits,
     })
 }
 
@@ -483,6 +504,7 @@ pub(super) async fn run_domain(
         domain_id,
         operator_id,
         domain_type_args,
+        transaction_pool_limits,
     } = domain_configuration;
 
     // Replace storage in the chain spec with correct one for this particular domain
@@ -602,6 +624,8 @@ pub(super) async fn run_domain(
                 consensus_chain_sync_params,
                 challenge_period: domains_block_pruning_depth,
                 domain_backend,
+                telemetry_buffer_size: None,
+                transaction_pool_limits: Some(transaction_pool_limits),
             };
 
             let mut domain_node = domain_service::new_full::<
@@ -641,6 +665,8 @@ pub(super) async fn run_domain(
                 consensus_chain_sync_params,
                 challenge_period: domains_block_pruning_depth,
                 domain_backend,
+                telemetry_buffer_size: None,
+                transaction_pool_limits: Some(transaction_pool_limits),
             };
 
             let mut domain_node = domain_service::new_full::<
@@ -108,13 +108,14 @@ use subspace_core_primitives::pot::PotSeed;
 use subspace_core_primitives::{BlockNumber, PublicKey, REWARD_SIGNING_CONTEXT};
 use subspace_erasure_coding::ErasureCoding;
 use subspace_kzg::Kzg;
+use subspace_networking::Node;
 use subspace_networking::libp2p::multiaddr::Protocol;
 use subspace_networking::utils::piece_provider::PieceProvider;
 use subspace_proof_of_space::Table;
 use subspace_runtime_primitives::opaque::Block;
 use subspace_runtime_primitives::{AccountId, Balance, BlockHashFor, Hash, Nonce};
 use tokio::sync::broadcast;
-use tracing::{Instrument, debug, error, info};
+use tracing::{Instrument, debug, error, info, warn};
 pub use utils::wait_for_block_import;
 
 // There are multiple places where it is assumed that node is running on 64-bit system, refuse to
@@ -715,6 +716,75 @@ where
         SubspaceNotificationStream<ArchivedSegmentNotification>,
     /// Transaction pool.
     pub transaction_pool: Arc<TransactionPoolHandle<Block, Client>>,
+    /// DSN node.
+    ///
+    /// Networking keeps running while node tasks hold their own clones, so dropping this handle
+    /// doesn't stop it.
+    pub dsn_node: Node,
+}
+
+impl<Client> NewFull<Client>
+where
+    Client: ProvideRuntimeApi<Block>
+        + AuxStore
+        + BlockBackend<Block>
+        + BlockIdTo<Block>
+        + HeaderBackend<Block>
+        + HeaderMetadata<Block, Error = sp_blockchain::Error>
+        + 'static,
+    Client::Api: TaggedTransactionQueue<Block>
+        + DomainsApi<Block, DomainHeader>
+        + FraudProofApi<Block, DomainHeader>
+        + SubspaceApi<Block, PublicKey>
+        + MmrApi<Block, H256, NumberFor<Block>>
+        + MessengerApi<Block, NumberFor<Block>, BlockHashFor<Block>>,
+{
+    /// Stop the node and wait for its tasks to finish.
+    ///
+    /// Drop order is part of the contract: every field except `task_manager` (client, network
+    /// service, sync service, RPC handlers, transaction pool, DSN node, etc.) is dropped first, and
+    /// only then the task manager is terminated, which signals all essential and non-essential tasks
+    /// to exit. Any clones of these components held by the caller must be dropped before calling
+    /// this method, otherwise tasks that depend on them may not finish in time.
+    ///
+    /// Returns `true` if all tasks finished before `timeout` elapsed, `false` otherwise (remaining
+    /// tasks will continue to wind down in the background).
+    pub async fn shutdown(self, timeout: Duration) -> bool {
+        let task_manager = {
+            // Everything except task manager is dropped at the end of this block
+            let node = self;
+            node.task_manager
+        };
+
+        shutdown_task_manager(task_manager, timeout).await
+    }
+}
+
+/// Terminate task manager and wait for its tasks to finish, see [`NewFull::shutdown`].
+async fn shutdown_task_manager(task_manager: TaskManager, timeout: Duration) -> bool {
+    let task_registry = task_manager.into_task_registry();
+    let wait_for_tasks = async {
+        while task_registry.running_tasks().values().sum::<usize>() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+
+    let finished = tokio::time::timeout(timeout, wait_for_tasks).await.is_ok();
+    if !finished {
+        let running_tasks = task_registry
+            .running_tasks()
+            .into_iter()
+            .map(|(task_kind, task_count)| {
+                (
+                    format!("{}-{}", task_kind.group, task_kind.name),
+                    task_count,
+                )
+            })
+            .collect::<Vec<_>>();
+        warn!(?running_tasks, ?timeout, "Node tasks didn't finish in time");
+    }
+
+    finished
 }
 
 type FullNode<RuntimeApi> = NewFull<FullClient<RuntimeApi>>;
@@ -1355,6 +1425,7 @@ where
         object_mapping_notification_stream,
         archived_segment_notification_stream,
         transaction_pool,
+        dsn_node: node,
     })
 }
 
@@ -1387,7 +1458,10 @@ fn extract_confirmation_depth(chain_spec: &dyn ChainSpec) -> Option<u32> {
 
 #[cfg(test)]
 mod test {
+    use crate::shutdown_task_manager;
+    use sc_service::TaskManager;
     use static_assertions::const_assert_eq;
+    use std::time::Duration;
     use subspace_data_retrieval::object_fetcher::MAX_BLOCK_LENGTH as ARCHIVER_MAX_BLOCK_LENGTH;
     use subspace_runtime_primitives::MAX_BLOCK_LENGTH as CONSENSUS_RUNTIME_MAX_BLOCK_LENGTH;
 
@@ -1400,4 +1474,30 @@ mod test {
             ARCHIVER_MAX_BLOCK_LENGTH,
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn shutdown_finishes_quick_tasks() {
+        let task_manager = TaskManager::new(tokio::runtime::Handle::current(), None).unwrap();
+        let spawn_handle = task_manager.spawn_handle();
+        spawn_handle.spawn("pending", None, futures::future::pending::<()>());
+        spawn_handle.spawn_blocking("quick", None, async {});
+
+        assert!(shutdown_task_manager(task_manager, Duration::from_secs(5)).await);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn shutdown_times_out_on_task_ignoring_exit() {
+        let task_manager = TaskManager::new(tokio::runtime::Handle::current(), None).unwrap();
+        let (started_sender, started_receiver) = futures::channel::oneshot::channel();
+        // Blocks the thread without ever yielding, so exit signal is never observed
+        task_manager
+            .spawn_handle()
+            .spawn_blocking("stubborn", None, async move {
+                let _ = started_sender.send(());
+                std::thread::sleep(Duration::from_secs(2));
+            });
+        started_receiver.await.unwrap();
+
+        assert!(!shutdown_task_manager(task_manager, Duration::from_millis(100)).await);
+    }
 }
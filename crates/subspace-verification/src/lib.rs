@@ -9,6 +9,9 @@
 #![feature(generic_const_exprs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(all(test, feature = "kzg", feature = "parallel"))]
+mod tests;
+
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
@@ -22,13 +25,17 @@ use core::num::NonZeroU64;
 #[cfg(feature = "kzg")]
 use core::simd::Simd;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+#[cfg(all(feature = "kzg", feature = "parallel"))]
+use rayon::prelude::*;
 use schnorrkel::SignatureError;
 use schnorrkel::context::SigningContext;
 #[cfg(feature = "kzg")]
 use subspace_core_primitives::hashes::blake3_254_hash_to_scalar;
 use subspace_core_primitives::hashes::{Blake3Hash, blake3_hash_list, blake3_hash_with_key};
 #[cfg(feature = "kzg")]
-use subspace_core_primitives::pieces::{PieceArray, Record, RecordWitness};
+use subspace_core_primitives::pieces::{
+    PieceArray, PieceIndex, Record, RecordCommitment, RecordWitness,
+};
 use subspace_core_primitives::pot::PotOutput;
 #[cfg(feature = "kzg")]
 use subspace_core_primitives::sectors::SectorId;
@@ -322,26 +329,21 @@ where
             }
         }
 
-        let position = sector_id
-            .derive_piece_index(
-                solution.piece_offset,
-                solution.history_size,
-                *max_pieces_in_sector,
-                *recent_segments,
-                *recent_history_fraction,
-            )
-            .position();
+        let piece_index = sector_id.derive_piece_index(
+            solution.piece_offset,
+            solution.history_size,
+            *max_pieces_in_sector,
+            *recent_segments,
+            *recent_history_fraction,
+        );
 
         // Check that piece is part of the blockchain history
-        if !is_record_commitment_hash_valid(
+        if !verify_record(
             kzg,
-            &Scalar::try_from(blake3_254_hash_to_scalar(
-                solution.record_commitment.as_ref(),
-            ))
-            .expect("Create correctly by dedicated hash function; qed"),
             segment_commitment,
+            piece_index,
+            &solution.record_commitment,
             &solution.record_witness,
-            position,
         ) {
             return Err(Error::InvalidPiece);
         }
@@ -350,6 +352,26 @@ where
     Ok(solution_distance)
 }
 
+/// Verify multiple solutions in parallel, see [`verify_solution()`] for details.
+///
+/// Results are returned in the same order as solutions were provided.
+#[cfg(all(feature = "kzg", feature = "parallel"))]
+pub fn verify_solutions_parallel<PosTable, RewardAddress>(
+    solutions: &[(&Solution<RewardAddress>, SlotNumber, &VerifySolutionParams)],
+    kzg: &Kzg,
+) -> Vec<Result<SolutionRange, Error>>
+where
+    PosTable: Table,
+    RewardAddress: Sync,
+{
+    solutions
+        .par_iter()
+        .map(|&(solution, slot, params)| {
+            verify_solution::<PosTable, _>(solution, slot, params, kzg)
+        })
+        .collect()
+}
+
 /// Validate witness embedded within a piece produced by archiver
 #[cfg(feature = "kzg")]
 pub fn is_piece_valid(
@@ -439,6 +461,29 @@ pub fn is_record_commitment_hash_valid(
     )
 }
 
+/// Verify that record commitment is part of the segment with provided segment commitment at the
+/// position corresponding to piece index
+#[cfg(feature = "kzg")]
+pub fn verify_record(
+    kzg: &Kzg,
+    segment_commitment: &SegmentCommitment,
+    piece_index: PieceIndex,
+    record_commitment: &RecordCommitment,
+    witness: &RecordWitness,
+) -> bool {
+    let record_commitment_hash =
+        Scalar::try_from(blake3_254_hash_to_scalar(record_commitment.as_ref()))
+            .expect("Create correctly by dedicated hash function; qed");
+
+    is_record_commitment_hash_valid(
+        kzg,
+        &record_commitment_hash,
+        segment_commitment,
+        witness,
+        piece_index.position(),
+    )
+}
+
 /// Derive proof of time entropy from chunk and proof of time for injection purposes.
 #[inline]
 pub fn derive_pot_entropy(chunk: &ScalarBytes, proof_of_time: PotOutput) -> Blake3Hash {
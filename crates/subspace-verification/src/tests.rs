@@ -0,0 +1,75 @@
+use crate::{Error, VerifySolutionParams, verify_solution, verify_solutions_parallel};
+use subspace_core_primitives::PublicKey;
+use subspace_core_primitives::pot::PotOutput;
+use subspace_core_primitives::sectors::SectorId;
+use subspace_core_primitives::solutions::{Solution, SolutionRange};
+use subspace_kzg::Kzg;
+use subspace_proof_of_space::shim::ShimTable;
+use subspace_proof_of_space::{Table, TableGenerator};
+
+#[test]
+fn parallel_verification_matches_sequential() {
+    let kzg = Kzg::new();
+    let params = VerifySolutionParams {
+        proof_of_time: PotOutput::default(),
+        solution_range: SolutionRange::MAX,
+        piece_check_params: None,
+    };
+
+    let solutions = (0..16_u8)
+        .map(|i| {
+            let slot = u64::from(i);
+            let mut solution =
+                Solution::genesis_solution(PublicKey::from([i; PublicKey::SIZE]), ());
+
+            // Make proof of space valid for every other solution, such that verification fails
+            // at different stages
+            if i % 2 == 0 {
+                let sector_id = SectorId::new(
+                    solution.public_key.hash(),
+                    solution.sector_index,
+                    solution.history_size,
+                );
+                let global_challenge = params
+                    .proof_of_time
+                    .derive_global_randomness()
+                    .derive_global_challenge(slot);
+                let s_bucket_audit_index = sector_id
+                    .derive_sector_slot_challenge(&global_challenge)
+                    .s_bucket_audit_index();
+                let table = ShimTable::generator()
+                    .generate(&sector_id.derive_evaluation_seed(solution.piece_offset));
+                if let Some(proof_of_space) = table.find_proof(s_bucket_audit_index.into()) {
+                    solution.proof_of_space = proof_of_space;
+                }
+            }
+
+            (solution, slot)
+        })
+        .collect::<Vec<_>>();
+    let solutions = solutions
+        .iter()
+        .map(|(solution, slot)| (solution, *slot, &params))
+        .collect::<Vec<_>>();
+
+    let sequential = solutions
+        .iter()
+        .map(|&(solution, slot, params)| {
+            verify_solution::<ShimTable, _>(solution, slot, params, &kzg)
+        })
+        .collect::<Vec<_>>();
+    let parallel = verify_solutions_parallel::<ShimTable, _>(&solutions, &kzg);
+
+    assert_eq!(sequential, parallel);
+    // Verification fails at different stages for different solutions
+    assert!(
+        sequential
+            .iter()
+            .any(|result| result == &Err(Error::InvalidProofOfSpace))
+    );
+    assert!(
+        sequential
+            .iter()
+            .any(|result| result != &Err(Error::InvalidProofOfSpace))
+    );
+}
@@ -7,15 +7,19 @@ use crate::fraud_proof::{FraudProofGenerator, TraceDiffType};
 use crate::tests::TxPoolError::InvalidTransaction as TxPoolInvalidTransaction;
 use cross_domain_message_gossip::get_channel_state;
 use domain_block_builder::BlockBuilderApi;
+use domain_block_preprocessor::inherents::CreateInherentDataProvider;
 use domain_runtime_primitives::opaque::Block as DomainBlock;
 use domain_runtime_primitives::{AccountId20Converter, AccountIdConverter, Hash};
+use domain_service::FullPool;
+use domain_service::providers::{BlockImportProvider, DefaultProvider, RpcProvider};
+use domain_service::rpc::{DomainHealthApiServer, DomainHealthRpc, FullDeps};
 use domain_test_primitives::{OnchainStateApi, TimestampApi};
 use domain_test_service::EcdsaKeyring::{self, Alice, Bob, Charlie, Dave, Eve};
 use domain_test_service::Sr25519Keyring::{self, Alice as Sr25519Alice, Ferdie};
 use domain_test_service::evm_domain_test_runtime::{
     Header, Runtime as TestRuntime, RuntimeCall, UncheckedExtrinsic as EvmUncheckedExtrinsic,
 };
-use domain_test_service::{EVM_DOMAIN_ID, EvmDomainNode};
+use domain_test_service::{AutoIdDomainClient, EVM_DOMAIN_ID, EvmDomainNode};
 use domain_test_utils::test_ethereum::{
     EvmAccountList, generate_evm_account_list, generate_evm_domain_call, generate_legacy_tx,
 };
@@ -23,11 +27,15 @@ use domain_test_utils::test_ethereum_tx::{AccountInfo, address_build, contract_a
 use ethereum::TransactionV3 as EthereumTransaction;
 use fp_rpc::EthereumRuntimeRPCApi;
 use futures::StreamExt;
+use jsonrpsee::RpcModule;
 use pallet_domains::{FraudProofFor, OpaqueBundleOf, OperatorConfig};
 use pallet_messenger::ChainAllowlistUpdate;
 use parity_scale_codec::{Decode, Encode};
 use sc_client_api::{Backend, BlockBackend, BlockchainEvents, HeaderBackend};
+use sc_consensus::DefaultImportQueue;
 use sc_domains::generate_mmr_proof;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_rpc_server::SubscriptionIdProvider;
 use sc_service::{BasePath, Role};
 use sc_transaction_pool_api::TransactionPool;
 use sc_transaction_pool_api::error::Error as TxPoolError;
@@ -72,6 +80,7 @@ use std::assert_matches;
 use std::collections::{BTreeMap, VecDeque};
 use std::future::IntoFuture;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use subspace_core_primitives::pot::PotOutput;
 use subspace_runtime_primitives::opaque::Block as CBlock;
@@ -82,6 +91,7 @@ use subspace_test_runtime::Runtime;
 use subspace_test_service::{
     MockConsensusNode, produce_block_with, produce_blocks, produce_blocks_until,
 };
+use substrate_prometheus_endpoint::Registry;
 use tempfile::TempDir;
 use tracing::{error, info};
 
@@ -1665,6 +1675,182 @@ async fn test_domain_block_production() {
     assert_eq!(alice.client.info().best_number, domain_block_number + 10);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_domain_health_rpc() {
+    let directory = TempDir::new().expect("Must be able to create temporary directory");
+
+    let mut builder = sc_cli::LoggerBuilder::new("");
+    builder.with_colors(false);
+    let _ = builder.init();
+
+    let tokio_handle = tokio::runtime::Handle::current();
+
+    // Start Ferdie
+    let mut ferdie = MockConsensusNode::run(
+        tokio_handle.clone(),
+        Ferdie,
+        BasePath::new(directory.path().join("ferdie")),
+    );
+
+    // Run Alice (a evm domain authority node)
+    let alice = domain_test_service::DomainNodeBuilder::new(
+        tokio_handle.clone(),
+        BasePath::new(directory.path().join("alice")),
+    )
+    .build_evm_node(Role::Authority, Alice, &mut ferdie)
+    .await;
+
+    let domain_health_rpc = DomainHealthRpc::new(alice.client.clone(), alice.sync_service.clone());
+
+    // Only genesis block, not ready yet
+    let health = domain_health_rpc.health().unwrap();
+    assert_eq!(health.best_number, 0);
+    assert!(!health.is_syncing);
+    assert!(!health.is_ready);
+
+    produce_blocks!(ferdie, alice, 3).await.unwrap();
+
+    let health = domain_health_rpc.health().unwrap();
+    assert_eq!(health.best_number, 3);
+    assert!(health.is_ready);
+
+    // The same method is registered in the default RPC set
+    let (response, _receiver) = alice
+        .rpc_handlers
+        .rpc_query(r#"{"jsonrpc":"2.0","id":1,"method":"domain_health","params":[]}"#)
+        .await
+        .unwrap();
+    assert!(response.contains(r#""bestNumber":3"#), "{response}");
+    assert!(response.contains(r#""isReady":true"#), "{response}");
+}
+
+type AutoIdFullPool = FullPool<auto_id_domain_test_runtime::RuntimeApi>;
+type AutoIdCreateInherentDataProvider =
+    CreateInherentDataProvider<subspace_test_client::Client, CBlock>;
+type AutoIdFullDeps = FullDeps<
+    DomainBlock,
+    AutoIdDomainClient,
+    AutoIdFullPool,
+    domain_test_service::Backend,
+    AutoIdCreateInherentDataProvider,
+>;
+
+/// Provider that behaves like [`DefaultProvider`], but records whether its import queue was built
+#[derive(Clone, Default)]
+struct ImportQueueTrackingProvider {
+    import_queue_built: Arc<AtomicBool>,
+}
+
+impl BlockImportProvider<DomainBlock, AutoIdDomainClient> for ImportQueueTrackingProvider {
+    type BI = Arc<AutoIdDomainClient>;
+
+    fn block_import(&self, client: Arc<AutoIdDomainClient>) -> Self::BI {
+        client
+    }
+
+    fn import_queue(
+        &self,
+        client: Arc<AutoIdDomainClient>,
+        spawner: &impl SpawnEssentialNamed,
+        registry: Option<&Registry>,
+    ) -> DefaultImportQueue<DomainBlock> {
+        self.import_queue_built.store(true, Ordering::Release);
+        DefaultProvider.import_queue(client, spawner, registry)
+    }
+}
+
+impl
+    RpcProvider<
+        DomainBlock,
+        AutoIdDomainClient,
+        AutoIdFullPool,
+        domain_test_service::Backend,
+        auto_id_domain_test_runtime::AccountId,
+        AutoIdCreateInherentDataProvider,
+    > for ImportQueueTrackingProvider
+{
+    type Deps = AutoIdFullDeps;
+
+    fn deps(&self, full_deps: AutoIdFullDeps) -> Result<Self::Deps, sc_service::Error> {
+        Ok(full_deps)
+    }
+
+    fn rpc_id(&self) -> Option<Box<dyn SubscriptionIdProvider>> {
+        None
+    }
+
+    fn rpc_builder<SE>(
+        &self,
+        deps: Self::Deps,
+        subscription_task_executor: SubscriptionTaskExecutor,
+        essential_task_spawner: SE,
+    ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
+    where
+        SE: SpawnEssentialNamed + Clone,
+    {
+        <DefaultProvider as RpcProvider<
+            DomainBlock,
+            AutoIdDomainClient,
+            AutoIdFullPool,
+            domain_test_service::Backend,
+            auto_id_domain_test_runtime::AccountId,
+            AutoIdCreateInherentDataProvider,
+        >>::rpc_builder(
+            &DefaultProvider,
+            deps,
+            subscription_task_executor,
+            essential_task_spawner,
+        )
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_domain_custom_import_queue() {
+    let directory = TempDir::new().expect("Must be able to create temporary directory");
+
+    let mut builder = sc_cli::LoggerBuilder::new("");
+    builder.with_colors(false);
+    let _ = builder.init();
+
+    let tokio_handle = tokio::runtime::Handle::current();
+
+    // Start Ferdie
+    let mut ferdie = MockConsensusNode::run(
+        tokio_handle.clone(),
+        Ferdie,
+        BasePath::new(directory.path().join("ferdie")),
+    );
+
+    // Run Alice (a evm domain authority node)
+    let alice = domain_test_service::DomainNodeBuilder::new(
+        tokio_handle.clone(),
+        BasePath::new(directory.path().join("alice")),
+    )
+    .build_evm_node(Role::Authority, Alice, &mut ferdie)
+    .await;
+
+    // Run Bob (a auto-id domain authority node) with custom import queue
+    let provider = ImportQueueTrackingProvider::default();
+    let bob = domain_test_service::DomainNodeBuilder::new(
+        tokio_handle.clone(),
+        BasePath::new(directory.path().join("bob")),
+    )
+    .build_auto_id_node_with_provider(
+        Role::Authority,
+        Sr25519Keyring::Bob,
+        &mut ferdie,
+        provider.clone(),
+    )
+    .await;
+
+    // Import queue of the provider is used instead of the default one
+    assert!(provider.import_queue_built.load(Ordering::Acquire));
+
+    // Node built with custom import queue keeps processing domain blocks
+    produce_blocks!(ferdie, alice, 3, bob).await.unwrap();
+    assert_eq!(bob.client.info().best_number, 3);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_processing_empty_consensus_block() {
     let directory = TempDir::new().expect("Must be able to create temporary directory");
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use sc_chain_spec::ChainSpec;
 use sc_network::config::{
     DEFAULT_KADEMLIA_REPLICATION_FACTOR, MultiaddrWithPeerId, NetworkBackendType,
@@ -50,6 +53,45 @@ pub struct SubstrateRpcConfiguration {
     pub disable_batch_requests: bool,
     /// Limit the max length per RPC batch request
     pub max_batch_request_len: Option<u32>,
+    /// Maximum RPC request payload size in MB, [`RPC_DEFAULT_MAX_REQUEST_SIZE_MB`] is used if
+    /// `None`
+    pub max_request_size: Option<u32>,
+}
+
+impl SubstrateRpcConfiguration {
+    fn batch_config(&self) -> RpcBatchRequestConfig {
+        if self.disable_batch_requests {
+            RpcBatchRequestConfig::Disabled
+        } else if let Some(l) = self.max_batch_request_len {
+            RpcBatchRequestConfig::Limit(l)
+        } else {
+            RpcBatchRequestConfig::Unlimited
+        }
+    }
+
+    /// RPC server endpoints, `None` if RPC server is disabled
+    fn rpc_endpoints(&self) -> Option<Vec<RpcEndpoint>> {
+        self.listen_on.map(|listen_addr| {
+            vec![RpcEndpoint {
+                batch_config: self.batch_config(),
+                max_connections: self.max_connections,
+                listen_addr,
+                rpc_methods: self.methods,
+                rate_limit: self.rate_limit,
+                rate_limit_trust_proxy_headers: self.rate_limit_trust_proxy_headers,
+                rate_limit_whitelisted_ips: self.rate_limit_whitelisted_ips.clone(),
+                max_payload_in_mb: self
+                    .max_request_size
+                    .unwrap_or(RPC_DEFAULT_MAX_REQUEST_SIZE_MB),
+                max_payload_out_mb: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB,
+                max_subscriptions_per_connection: self.max_subscriptions_per_connection,
+                max_buffer_capacity_per_connection: self.message_buffer_capacity_per_connection,
+                cors: self.cors.clone(),
+                retry_random_port: true,
+                is_optional: false,
+            }]
+        })
+    }
 }
 
 /// Simplified network used in Substrate
@@ -112,6 +154,12 @@ pub struct SubstrateConfiguration {
     pub chain_spec: Box<dyn ChainSpec>,
     /// Executor configuration
     pub executor: ExecutorConfiguration,
+    /// Directory with WASM runtimes that override on-chain runtimes.
+    ///
+    /// Every `*.wasm` file in the directory is loaded, file names don't matter. The override is
+    /// used instead of the on-chain runtime whose `spec_name` and `spec_version` match those
+    /// embedded in the WASM blob.
+    pub wasm_runtime_overrides: Option<PathBuf>,
     /// Trie cache size
     pub trie_cache_size: Option<usize>,
 }
@@ -122,40 +170,8 @@ impl From<SubstrateConfiguration> for Configuration {
             + configuration.network.default_peers_set.out_peers;
         let client_version = format!("{}/{}", configuration.impl_name, configuration.impl_version);
 
-        let rpc_batch_config = if configuration.rpc_options.disable_batch_requests {
-            RpcBatchRequestConfig::Disabled
-        } else if let Some(l) = configuration.rpc_options.max_batch_request_len {
-            RpcBatchRequestConfig::Limit(l)
-        } else {
-            RpcBatchRequestConfig::Unlimited
-        };
-        let rpc_addr = configuration.rpc_options.listen_on.map(|listen_addr| {
-            vec![RpcEndpoint {
-                batch_config: rpc_batch_config,
-                max_connections: configuration.rpc_options.max_connections,
-                listen_addr,
-                rpc_methods: configuration.rpc_options.methods,
-                rate_limit: configuration.rpc_options.rate_limit,
-                rate_limit_trust_proxy_headers: configuration
-                    .rpc_options
-                    .rate_limit_trust_proxy_headers,
-                rate_limit_whitelisted_ips: configuration
-                    .rpc_options
-                    .rate_limit_whitelisted_ips
-                    .clone(),
-                max_payload_in_mb: RPC_DEFAULT_MAX_REQUEST_SIZE_MB,
-                max_payload_out_mb: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB,
-                max_subscriptions_per_connection: configuration
-                    .rpc_options
-                    .max_subscriptions_per_connection,
-                max_buffer_capacity_per_connection: configuration
-                    .rpc_options
-                    .message_buffer_capacity_per_connection,
-                cors: configuration.rpc_options.cors.clone(),
-                retry_random_port: true,
-                is_optional: false,
-            }]
-        });
+        let rpc_batch_config = configuration.rpc_options.batch_config();
+        let rpc_addr = configuration.rpc_options.rpc_endpoints();
 
         Self {
             impl_name: configuration.impl_name,
@@ -215,13 +231,16 @@ impl From<SubstrateConfiguration> for Configuration {
             state_pruning: Some(configuration.state_pruning),
             blocks_pruning: configuration.blocks_pruning,
             executor: configuration.executor,
-            wasm_runtime_overrides: None,
+            wasm_runtime_overrides: configuration.wasm_runtime_overrides,
             rpc: RpcConfiguration {
                 addr: rpc_addr,
                 methods: configuration.rpc_options.methods,
                 max_connections: configuration.rpc_options.max_connections,
                 cors: configuration.rpc_options.cors,
-                max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB,
+                max_request_size: configuration
+                    .rpc_options
+                    .max_request_size
+                    .unwrap_or(RPC_DEFAULT_MAX_REQUEST_SIZE_MB),
                 max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB,
                 id_provider: None,
                 max_subs_per_conn: configuration.rpc_options.max_subscriptions_per_connection,
@@ -247,7 +266,8 @@ impl From<SubstrateConfiguration> for Configuration {
                     )
                 }),
             telemetry_endpoints: configuration.telemetry_endpoints,
-            // Offchain worker is not used in Subspace
+            // Offchain worker is not used in Subspace, domains that need it can enable it in the
+            // resulting configuration
             offchain_worker: OffchainWorkerConfig {
                 enabled: false,
                 indexing_enabled: false,
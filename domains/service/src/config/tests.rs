@@ -0,0 +1,139 @@
+use crate::config::{
+    RPC_DEFAULT_MAX_REQUEST_SIZE_MB, SubstrateConfiguration, SubstrateNetworkConfiguration,
+    SubstrateRpcConfiguration,
+};
+use domain_runtime_primitives::opaque::Block;
+use evm_domain_test_runtime::{RuntimeApi, VERSION, WASM_BINARY, WASM_BINARY_BLOATY};
+use sc_chain_spec::{ChainType, GenericChainSpec, NoExtension};
+use sc_domains::RuntimeExecutor;
+use sc_network::config::{NodeKeyConfig, Secret, SetConfig};
+use sc_service::config::{ExecutorConfiguration, KeystoreConfig};
+use sc_service::{BlocksPruning, Configuration, PruningMode, RpcMethods, TransactionPoolOptions};
+use sp_blockchain::HeaderBackend;
+use sp_version::RuntimeVersion;
+use sp_version::embed::embed_runtime_version;
+use std::fs;
+use tempfile::TempDir;
+
+fn rpc_configuration() -> SubstrateRpcConfiguration {
+    SubstrateRpcConfiguration {
+        listen_on: Some("127.0.0.1:9944".parse().unwrap()),
+        max_connections: 100,
+        cors: None,
+        methods: RpcMethods::Safe,
+        rate_limit: None,
+        rate_limit_whitelisted_ips: Vec::new(),
+        rate_limit_trust_proxy_headers: false,
+        max_subscriptions_per_connection: 1024,
+        message_buffer_capacity_per_connection: 64,
+        disable_batch_requests: false,
+        max_batch_request_len: None,
+        max_request_size: None,
+    }
+}
+
+#[test]
+fn rpc_endpoint_defaults() {
+    let endpoints = rpc_configuration().rpc_endpoints().unwrap();
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(endpoints[0].cors, None);
+    assert_eq!(
+        endpoints[0].max_payload_in_mb,
+        RPC_DEFAULT_MAX_REQUEST_SIZE_MB
+    );
+
+    // No endpoints without listen address
+    let rpc_configuration = SubstrateRpcConfiguration {
+        listen_on: None,
+        ..rpc_configuration()
+    };
+    assert!(rpc_configuration.rpc_endpoints().is_none());
+}
+
+#[test]
+fn rpc_endpoint_cors_and_max_request_size() {
+    let cors = vec![
+        "http://localhost:3000".to_string(),
+        "https://example.com".to_string(),
+    ];
+    let rpc_configuration = SubstrateRpcConfiguration {
+        cors: Some(cors.clone()),
+        max_request_size: Some(1),
+        ..rpc_configuration()
+    };
+
+    let endpoints = rpc_configuration.rpc_endpoints().unwrap();
+    assert_eq!(endpoints[0].cors, Some(cors));
+    assert_eq!(endpoints[0].max_payload_in_mb, 1);
+}
+
+#[tokio::test]
+async fn wasm_runtime_overrides() {
+    let base_path = TempDir::new().unwrap();
+    let wasm_runtime_overrides = TempDir::new().unwrap();
+
+    // Override has the same spec version as the on-chain runtime, implementation version is
+    // different to tell which one is used
+    let override_version = RuntimeVersion {
+        impl_version: VERSION.impl_version + 1,
+        ..VERSION
+    };
+    let override_code =
+        embed_runtime_version(WASM_BINARY_BLOATY.unwrap(), override_version.clone()).unwrap();
+    fs::write(
+        wasm_runtime_overrides.path().join("runtime.wasm"),
+        override_code,
+    )
+    .unwrap();
+
+    let chain_spec = GenericChainSpec::<NoExtension, ()>::builder(WASM_BINARY.unwrap(), None)
+        .with_name("Test")
+        .with_id("test")
+        .with_chain_type(ChainType::Development)
+        .with_genesis_config_patch(serde_json::json!({}))
+        .build();
+
+    let configuration = Configuration::from(SubstrateConfiguration {
+        impl_name: "domain-test-node".to_string(),
+        impl_version: "0.1".to_string(),
+        operator: false,
+        base_path: base_path.path().to_path_buf(),
+        transaction_pool: TransactionPoolOptions::default(),
+        network: SubstrateNetworkConfiguration {
+            listen_on: Vec::new(),
+            public_addresses: Vec::new(),
+            bootstrap_nodes: Vec::new(),
+            node_key: NodeKeyConfig::Ed25519(Secret::New),
+            default_peers_set: SetConfig::default(),
+            node_name: "test".to_string(),
+            allow_private_ips: false,
+            force_synced: false,
+        },
+        keystore: KeystoreConfig::InMemory,
+        state_pruning: PruningMode::ArchiveAll,
+        blocks_pruning: BlocksPruning::KeepAll,
+        rpc_options: rpc_configuration(),
+        prometheus_listen_on: None,
+        telemetry_endpoints: None,
+        force_authoring: false,
+        chain_spec: Box::new(chain_spec),
+        executor: ExecutorConfiguration::default(),
+        wasm_runtime_overrides: Some(wasm_runtime_overrides.path().to_path_buf()),
+        trie_cache_size: None,
+    });
+
+    let executor = sc_service::new_wasm_executor(&configuration.executor);
+    let (client, _backend, _keystore_container, _task_manager) =
+        sc_service::new_full_parts::<Block, RuntimeApi, RuntimeExecutor>(
+            &configuration,
+            None,
+            executor,
+        )
+        .unwrap();
+
+    let runtime_version = client
+        .runtime_version_at(client.info().genesis_hash)
+        .unwrap();
+    assert_eq!(runtime_version.spec_version, VERSION.spec_version);
+    assert_eq!(runtime_version.impl_version, override_version.impl_version);
+}
@@ -1,3 +1,7 @@
+#[cfg(test)]
+mod tests;
+
+use crate::metrics::DomainMetrics;
 use crate::network::build_network;
 use crate::providers::{BlockImportProvider, RpcProvider};
 use crate::{FullBackend, FullClient};
@@ -9,14 +13,14 @@ use domain_client_operator::snap_sync::ConsensusChainSyncParams;
 use domain_client_operator::{Operator, OperatorParams, OperatorStreams};
 use domain_runtime_primitives::opaque::{Block, Header};
 use domain_runtime_primitives::{Balance, Hash};
-use futures::Stream;
 use futures::channel::mpsc;
+use futures::{FutureExt, Stream};
 use pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi;
 use sc_client_api::{
-    AuxStore, BlockBackend, BlockImportNotification, BlockchainEvents, ExecutorProvider,
+    AuxStore, Backend, BlockBackend, BlockImportNotification, BlockchainEvents, ExecutorProvider,
     ProofProvider,
 };
-use sc_consensus::{BasicQueue, BoxBlockImport};
+use sc_consensus::BoxBlockImport;
 use sc_domains::{ExtensionsFactory, RuntimeExecutor};
 use sc_network::service::traits::NetworkService;
 use sc_network::{NetworkPeers, NetworkWorker, NotificationMetrics};
@@ -25,7 +29,7 @@ use sc_service::{
     TFullBackend, TaskManager,
 };
 use sc_telemetry::{Telemetry, TelemetryWorker, TelemetryWorkerHandle};
-use sc_transaction_pool::{BasicPool, FullChainApi};
+use sc_transaction_pool::{BasicPool, FullChainApi, Options as PoolOptions, PoolLimit};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sc_utils::mpsc::{TracingUnboundedReceiver, tracing_unbounded};
 use serde::de::DeserializeOwned;
@@ -48,11 +52,13 @@ use sp_session::SessionKeys;
 use sp_transaction_pool::runtime_api::TaggedTransactionQueue;
 use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
+use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::Arc;
 use subspace_core_primitives::pot::PotOutput;
 use subspace_runtime_primitives::{HeaderFor, Nonce};
 use substrate_frame_rpc_system::AccountNonceApi;
+use tracing::error;
 
 pub type DomainOperator<Block, CBlock, CClient, RuntimeApi> = Operator<
     Block,
@@ -111,16 +117,78 @@ where
     pub operator: DomainOperator<Block, CBlock, CClient, RuntimeApi>,
     /// Transaction pool
     pub transaction_pool: Arc<FullPool<RuntimeApi>>,
+    /// Domain metrics, present if Prometheus registry is configured
+    pub metrics: Option<DomainMetrics>,
 
     _phantom_data: PhantomData<AccountId>,
 }
 
+/// Default size of the telemetry worker buffer
+pub const DEFAULT_TELEMETRY_BUFFER_SIZE: NonZeroUsize =
+    NonZeroUsize::new(16).expect("Not zero; qed");
+
+/// Limits of the domain transaction pool, override Substrate's defaults when specified
+#[derive(Debug, Clone, Copy)]
+pub struct TransactionPoolLimits {
+    /// Maximum number of transactions in the ready queue, must be greater than zero
+    pub ready_count: usize,
+    /// Maximum total size of transactions in the ready queue, in bytes
+    pub ready_total_bytes: usize,
+    /// Maximum number of transactions in the future queue
+    pub future_count: usize,
+    /// Maximum total size of transactions in the future queue, in bytes
+    pub future_total_bytes: usize,
+}
+
+impl TransactionPoolLimits {
+    /// Limits for the whole pool, split between ready and future queues the same way as in
+    /// Substrate's `TransactionPoolOptions` created from `--pool-limit` and `--pool-kbytes`
+    pub fn new(pool_limit: usize, pool_total_bytes: usize) -> Self {
+        /// Future queue gets this fraction of ready queue limits
+        const FUTURE_QUEUE_FACTOR: usize = 10;
+
+        Self {
+            ready_count: pool_limit,
+            ready_total_bytes: pool_total_bytes,
+            future_count: pool_limit / FUTURE_QUEUE_FACTOR,
+            future_total_bytes: pool_total_bytes / FUTURE_QUEUE_FACTOR,
+        }
+    }
+}
+
+/// Transaction pool options with optional limits applied on top of the defaults
+#[expect(clippy::result_large_err, reason = "Comes from Substrate")]
+fn transaction_pool_options(
+    transaction_pool_limits: Option<TransactionPoolLimits>,
+) -> Result<PoolOptions, sc_service::Error> {
+    let mut options = PoolOptions::default();
+
+    if let Some(limits) = transaction_pool_limits {
+        if limits.ready_count == 0 {
+            return Err(sc_service::Error::Other(
+                "Transaction pool must allow at least one ready transaction".to_string(),
+            ));
+        }
+
+        options.ready = PoolLimit {
+            count: limits.ready_count,
+            total_bytes: limits.ready_total_bytes,
+        };
+        options.future = PoolLimit {
+            count: limits.future_count,
+            total_bytes: limits.future_total_bytes,
+        };
+    }
+
+    Ok(options)
+}
+
 /// A transaction pool for a full node.
 pub type FullPool<RuntimeApi> =
     BasicPool<FullChainApi<FullClient<Block, RuntimeApi>, Block>, Block>;
 
 /// Constructs a partial domain node.
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 #[expect(clippy::result_large_err, reason = "Comes from Substrate")]
 fn new_partial<RuntimeApi, CBlock, CClient, BIMP>(
     domain_id: DomainId,
@@ -130,6 +198,8 @@ fn new_partial<RuntimeApi, CBlock, CClient, BIMP>(
     block_import_provider: &BIMP,
     confirmation_depth_k: NumberFor<CBlock>,
     snap_sync: bool,
+    telemetry_buffer_size: Option<NonZeroUsize>,
+    transaction_pool_limits: Option<TransactionPoolLimits>,
 ) -> Result<
     PartialComponents<
         FullClient<Block, RuntimeApi>,
@@ -170,7 +240,11 @@ where
         .clone()
         .filter(|x| !x.is_empty())
         .map(|endpoints| -> Result<_, sc_telemetry::Error> {
-            let worker = TelemetryWorker::new(16)?;
+            let worker = TelemetryWorker::new(
+                telemetry_buffer_size
+                    .unwrap_or(DEFAULT_TELEMETRY_BUFFER_SIZE)
+                    .get(),
+            )?;
             let telemetry = worker.handle().new_telemetry(endpoints);
             Ok((worker, telemetry))
         })
@@ -218,17 +292,15 @@ where
     });
 
     let transaction_pool = Arc::from(BasicPool::new_full(
-        Default::default(),
+        transaction_pool_options(transaction_pool_limits)?,
         config.role.is_authority().into(),
         config.prometheus_registry(),
         task_manager.spawn_essential_handle(),
         client.clone(),
     ));
 
-    let import_queue = BasicQueue::new(
-        domain_client_consensus_relay_chain::Verifier::default(),
-        Box::new(block_import_provider.block_import(client.clone())),
-        None,
+    let import_queue = block_import_provider.import_queue(
+        client.clone(),
         &task_manager.spawn_essential_handle(),
         config.prometheus_registry(),
     );
@@ -274,6 +346,10 @@ where
     pub challenge_period: NumberFor<CBlock>,
     pub consensus_chain_sync_params: Option<ConsensusChainSyncParams<CBlock, HeaderFor<Block>>>,
     pub domain_backend: Arc<FullBackend<Block>>,
+    /// Size of the telemetry worker buffer, [`DEFAULT_TELEMETRY_BUFFER_SIZE`] is used if `None`
+    pub telemetry_buffer_size: Option<NonZeroUsize>,
+    /// Transaction pool limits, Substrate's defaults are used if `None`
+    pub transaction_pool_limits: Option<TransactionPoolLimits>,
 }
 
 /// Builds service for a domain full node.
@@ -364,6 +440,8 @@ where
         consensus_chain_sync_params,
         challenge_period,
         domain_backend,
+        telemetry_buffer_size,
+        transaction_pool_limits,
     } = domain_params;
 
     // TODO: Do we even need block announcement on domain node?
@@ -377,6 +455,8 @@ where
         &provider,
         confirmation_depth_k,
         consensus_chain_sync_params.is_some(),
+        telemetry_buffer_size,
+        transaction_pool_limits,
     )?;
 
     let (mut telemetry, _telemetry_worker_handle, code_executor, block_import) = params.other;
@@ -423,6 +503,8 @@ where
     let fork_id = domain_config.chain_spec.fork_id().map(String::from);
 
     let is_authority = domain_config.role.is_authority();
+    let prometheus_registry = domain_config.prometheus_registry().cloned();
+    let offchain_worker_enabled = domain_config.offchain_worker.enabled;
     domain_config.rpc.id_provider = provider.rpc_id();
     let rpc_builder = {
         let deps = crate::rpc::FullDeps {
@@ -431,7 +513,7 @@ where
             network: network_service.clone(),
             sync: sync_service.clone(),
             is_authority,
-            prometheus_registry: domain_config.prometheus_registry().cloned(),
+            prometheus_registry: prometheus_registry.clone(),
             database_source: domain_config.database.clone(),
             task_spawner: task_manager.spawn_handle(),
             backend: backend.clone(),
@@ -479,6 +561,48 @@ where
         tracing_execute_block: None,
     })?;
 
+    if offchain_worker_enabled {
+        let offchain_workers =
+            sc_offchain::OffchainWorkers::new(sc_offchain::OffchainWorkerOptions {
+                runtime_api_provider: client.clone(),
+                is_validator: is_authority,
+                keystore: Some(params.keystore_container.keystore()),
+                offchain_db: backend.offchain_storage(),
+                transaction_pool: Some(OffchainTransactionPoolFactory::new(
+                    transaction_pool.clone(),
+                )),
+                network_provider: Arc::new(network_service.clone()),
+                enable_http_requests: true,
+                custom_extensions: |_| vec![],
+            })?;
+        task_manager.spawn_handle().spawn(
+            "domain-offchain-workers-runner",
+            "offchain-worker",
+            offchain_workers
+                .run(client.clone(), task_manager.spawn_handle())
+                .boxed(),
+        );
+    }
+
+    let metrics = match prometheus_registry.as_ref().map(DomainMetrics::register) {
+        Some(Ok(metrics)) => {
+            task_manager.spawn_handle().spawn(
+                "domain-metrics",
+                None,
+                metrics
+                    .clone()
+                    .run(client.clone(), client.every_import_notification_stream())
+                    .boxed(),
+            );
+            Some(metrics)
+        }
+        Some(Err(error)) => {
+            error!("Failed to initialize domain metrics: {error:?}");
+            None
+        }
+        None => None,
+    };
+
     let spawn_essential = task_manager.spawn_essential_handle();
     let (bundle_sender, _bundle_receiver) = tracing_unbounded("domain_bundle_stream", 100);
 
@@ -573,6 +697,7 @@ where
         rpc_handlers,
         operator,
         transaction_pool: params.transaction_pool,
+        metrics,
         _phantom_data: Default::default(),
     };
 
@@ -0,0 +1,51 @@
+use crate::domain::{TransactionPoolLimits, transaction_pool_options};
+use sc_transaction_pool::Options as PoolOptions;
+
+#[test]
+fn transaction_pool_limits() {
+    let default_options = PoolOptions::default();
+
+    // Defaults are used when no limits are specified
+    let options = transaction_pool_options(None).unwrap();
+    assert_eq!(options.ready.count, default_options.ready.count);
+    assert_eq!(options.ready.total_bytes, default_options.ready.total_bytes);
+    assert_eq!(options.future.count, default_options.future.count);
+    assert_eq!(
+        options.future.total_bytes,
+        default_options.future.total_bytes
+    );
+
+    // Custom limits override defaults
+    let options = transaction_pool_options(Some(TransactionPoolLimits {
+        ready_count: 100,
+        ready_total_bytes: 1024 * 1024,
+        future_count: 10,
+        future_total_bytes: 1024,
+    }))
+    .unwrap();
+    assert_eq!(options.ready.count, 100);
+    assert_eq!(options.ready.total_bytes, 1024 * 1024);
+    assert_eq!(options.future.count, 10);
+    assert_eq!(options.future.total_bytes, 1024);
+    assert_eq!(options.ban_time, default_options.ban_time);
+
+    // Empty ready queue is not allowed
+    assert!(
+        transaction_pool_options(Some(TransactionPoolLimits {
+            ready_count: 0,
+            ready_total_bytes: 1024 * 1024,
+            future_count: 10,
+            future_total_bytes: 1024,
+        }))
+        .is_err()
+    );
+}
+
+#[test]
+fn transaction_pool_limits_split() {
+    let limits = TransactionPoolLimits::new(8192, 20 * 1024 * 1024);
+    assert_eq!(limits.ready_count, 8192);
+    assert_eq!(limits.ready_total_bytes, 20 * 1024 * 1024);
+    assert_eq!(limits.future_count, 819);
+    assert_eq!(limits.future_total_bytes, 2 * 1024 * 1024);
+}
@@ -2,11 +2,15 @@
 
 pub mod config;
 mod domain;
+pub mod metrics;
 pub mod network;
 pub mod providers;
 pub mod rpc;
 
-pub use self::domain::{DomainOperator, DomainParams, FullPool, NewFull, new_full};
+pub use self::domain::{
+    DEFAULT_TELEMETRY_BUFFER_SIZE, DomainOperator, DomainParams, FullPool, NewFull,
+    TransactionPoolLimits, new_full,
+};
 use sc_domains::RuntimeExecutor;
 use sc_service::TFullClient;
 
@@ -0,0 +1,90 @@
+//! Domain node metrics
+
+#[cfg(test)]
+mod tests;
+
+use futures::StreamExt;
+use parity_scale_codec::Encode;
+use sc_client_api::{BlockBackend, BlockImportNotification, ImportNotifications};
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+use substrate_prometheus_endpoint::{Counter, PrometheusError, Registry, U64, register};
+
+/// Domain-specific metrics.
+///
+/// Metrics are cheap to clone, clones share the same underlying counters, so other components of
+/// the domain node can update them too.
+#[derive(Clone)]
+pub struct DomainMetrics {
+    /// Total number of imported domain blocks
+    pub blocks: Counter<U64>,
+    /// Total number of extrinsics in the imported domain blocks
+    pub extrinsics: Counter<U64>,
+    /// Total extrinsic bytes in the imported domain blocks
+    pub extrinsics_size: Counter<U64>,
+}
+
+impl DomainMetrics {
+    /// Create new metrics and register them in the provided registry
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            blocks: register(
+                Counter::new(
+                    "subspace_domain_blocks",
+                    "Total number of imported domain blocks",
+                )?,
+                registry,
+            )?,
+            extrinsics: register(
+                Counter::new(
+                    "subspace_domain_extrinsics",
+                    "Total number of extrinsics in the imported domain blocks",
+                )?,
+                registry,
+            )?,
+            extrinsics_size: register(
+                Counter::new(
+                    "subspace_domain_extrinsics_size",
+                    "Total extrinsic bytes in the imported domain blocks",
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    /// Update metrics on every imported block until block import notifications stream ends
+    pub(crate) async fn run<Block, Client>(
+        self,
+        client: Arc<Client>,
+        mut block_import: ImportNotifications<Block>,
+    ) where
+        Block: BlockT,
+        Client: BlockBackend<Block>,
+    {
+        while let Some(incoming_block) = block_import.next().await {
+            self.update_block_metrics(client.as_ref(), incoming_block);
+        }
+    }
+
+    fn update_block_metrics<Block, Client>(
+        &self,
+        client: &Client,
+        incoming_block: BlockImportNotification<Block>,
+    ) where
+        Block: BlockT,
+        Client: BlockBackend<Block>,
+    {
+        let extrinsics = client
+            .block_body(incoming_block.hash)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        self.blocks.inc();
+        self.extrinsics.inc_by(extrinsics.len() as u64);
+        let total_size: usize = extrinsics
+            .iter()
+            .map(|extrinsic| extrinsic.encoded_size())
+            .sum();
+        self.extrinsics_size.inc_by(total_size as u64);
+    }
+}
@@ -0,0 +1,29 @@
+use crate::metrics::DomainMetrics;
+use substrate_prometheus_endpoint::Registry;
+
+#[test]
+fn metrics_registration() {
+    let registry = Registry::new();
+    let metrics = DomainMetrics::register(&registry).unwrap();
+
+    // Clones share the same counters
+    metrics.clone().blocks.inc();
+    metrics.extrinsics.inc_by(3);
+
+    let metric_families = registry.gather();
+    let value_of = |name: &str| {
+        metric_families
+            .iter()
+            .find(|metric_family| metric_family.get_name() == name)
+            .unwrap_or_else(|| panic!("Metric {name} must be registered"))
+            .get_metric()[0]
+            .get_counter()
+            .get_value()
+    };
+    assert_eq!(value_of("subspace_domain_blocks"), 1.0);
+    assert_eq!(value_of("subspace_domain_extrinsics"), 3.0);
+    assert_eq!(value_of("subspace_domain_extrinsics_size"), 0.0);
+
+    // The same metrics can't be registered twice in the same registry
+    assert!(DomainMetrics::register(&registry).is_err());
+}
@@ -1,10 +1,13 @@
+#[cfg(test)]
+mod tests;
+
 use crate::FullClient;
 use crate::rpc::FullDeps;
 use domain_runtime_primitives::{Balance, Nonce};
 use jsonrpsee::RpcModule;
 use parity_scale_codec::{Decode, Encode};
 use sc_client_api::{AuxStore, Backend, BlockBackend, StorageProvider};
-use sc_consensus::BlockImport;
+use sc_consensus::{BasicQueue, BlockImport, DefaultImportQueue};
 use sc_rpc::SubscriptionTaskExecutor;
 use sc_rpc_server::SubscriptionIdProvider;
 use sc_transaction_pool_api::TransactionPool;
@@ -18,6 +21,7 @@ use std::error::Error;
 use std::fmt::{Debug, Display};
 use std::sync::Arc;
 use substrate_frame_rpc_system::AccountNonceApi;
+use substrate_prometheus_endpoint::Registry;
 
 pub trait BlockImportProvider<Block: BlockT, Client>
 where
@@ -25,6 +29,25 @@ where
 {
     type BI: BlockImport<Block, Error = sp_consensus::Error> + Send + Sync + 'static;
     fn block_import(&self, client: Arc<Client>) -> Self::BI;
+
+    /// Build import queue for domain blocks.
+    ///
+    /// By default blocks are imported using [`Self::block_import()`] and verified with relay chain
+    /// verifier, which accepts all blocks since domain blocks are derived from consensus blocks.
+    fn import_queue(
+        &self,
+        client: Arc<Client>,
+        spawner: &impl SpawnEssentialNamed,
+        registry: Option<&Registry>,
+    ) -> DefaultImportQueue<Block> {
+        BasicQueue::new(
+            domain_client_consensus_relay_chain::Verifier::default(),
+            Box::new(self.block_import(client)),
+            None,
+            spawner,
+            registry,
+        )
+    }
 }
 
 #[derive(Clone, Default)]
@@ -120,3 +143,108 @@ where
         crate::rpc::create_full::<_, _, _, AccountId, BE, CIDP>(deps)
     }
 }
+
+/// RPC provider that extends RPC methods of the inner provider with custom RPC extensions.
+///
+/// `rpc_extensions_builder` is called every time RPC module is built by the inner provider and
+/// resulting RPC module is merged into it, methods must not conflict with those of the inner
+/// provider.
+#[derive(Clone)]
+pub struct RpcExtensionsProvider<Provider, RpcExtensionsBuilder> {
+    inner: Provider,
+    rpc_extensions_builder: RpcExtensionsBuilder,
+}
+
+impl<Provider, RpcExtensionsBuilder> RpcExtensionsProvider<Provider, RpcExtensionsBuilder>
+where
+    RpcExtensionsBuilder:
+        Fn(SubscriptionTaskExecutor) -> Result<RpcModule<()>, Box<dyn Error + Send + Sync>>,
+{
+    /// Create new instance, use [`DefaultProvider`] as `inner` to extend default RPC methods
+    pub fn new(inner: Provider, rpc_extensions_builder: RpcExtensionsBuilder) -> Self {
+        Self {
+            inner,
+            rpc_extensions_builder,
+        }
+    }
+}
+
+impl<Block, Client, Provider, RpcExtensionsBuilder> BlockImportProvider<Block, Client>
+    for RpcExtensionsProvider<Provider, RpcExtensionsBuilder>
+where
+    Block: BlockT,
+    Client: ProvideRuntimeApi<Block>,
+    Provider: BlockImportProvider<Block, Client>,
+{
+    type BI = Provider::BI;
+
+    fn block_import(&self, client: Arc<Client>) -> Self::BI {
+        self.inner.block_import(client)
+    }
+
+    fn import_queue(
+        &self,
+        client: Arc<Client>,
+        spawner: &impl SpawnEssentialNamed,
+        registry: Option<&Registry>,
+    ) -> DefaultImportQueue<Block> {
+        self.inner.import_queue(client, spawner, registry)
+    }
+}
+
+impl<Block, Client, BE, TxPool, AccountId, CIDP, Provider, RpcExtensionsBuilder>
+    RpcProvider<Block, Client, TxPool, BE, AccountId, CIDP>
+    for RpcExtensionsProvider<Provider, RpcExtensionsBuilder>
+where
+    Block: BlockT,
+    Client: ProvideRuntimeApi<Block> + StorageProvider<Block, BE>,
+    Client::Api: AccountNonceApi<Block, AccountId, Nonce>,
+    TxPool: TransactionPool<Block = Block> + Sync + Send + 'static,
+    BE: Backend<Block> + 'static,
+    AccountId: DeserializeOwned + Encode + Debug + Decode + Display + Clone + Sync + Send + 'static,
+    Provider: RpcProvider<Block, Client, TxPool, BE, AccountId, CIDP>,
+    RpcExtensionsBuilder:
+        Fn(SubscriptionTaskExecutor) -> Result<RpcModule<()>, Box<dyn Error + Send + Sync>>,
+{
+    type Deps = Provider::Deps;
+
+    fn deps(
+        &self,
+        full_deps: FullDeps<Block, Client, TxPool, BE, CIDP>,
+    ) -> Result<Self::Deps, sc_service::Error> {
+        self.inner.deps(full_deps)
+    }
+
+    fn rpc_id(&self) -> Option<Box<dyn SubscriptionIdProvider>> {
+        self.inner.rpc_id()
+    }
+
+    fn rpc_builder<SE>(
+        &self,
+        deps: Self::Deps,
+        subscription_task_executor: SubscriptionTaskExecutor,
+        essential_task_spawner: SE,
+    ) -> Result<RpcModule<()>, Box<dyn Error + Send + Sync>>
+    where
+        SE: SpawnEssentialNamed + Clone,
+    {
+        let module = self.inner.rpc_builder(
+            deps,
+            subscription_task_executor.clone(),
+            essential_task_spawner,
+        )?;
+        let extensions = (self.rpc_extensions_builder)(subscription_task_executor)?;
+
+        merge_rpc_extensions(module, extensions)
+    }
+}
+
+/// Merge RPC extensions into the base RPC module, failing if any method name is already taken.
+fn merge_rpc_extensions(
+    mut module: RpcModule<()>,
+    extensions: RpcModule<()>,
+) -> Result<RpcModule<()>, Box<dyn Error + Send + Sync>> {
+    module.merge(extensions)?;
+
+    Ok(module)
+}
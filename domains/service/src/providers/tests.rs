@@ -0,0 +1,133 @@
+use crate::providers::{RpcExtensionsProvider, RpcProvider, merge_rpc_extensions};
+use crate::rpc::FullDeps;
+use crate::{FullBackend, FullClient, FullPool};
+use domain_runtime_primitives::Nonce;
+use domain_runtime_primitives::opaque::Block;
+use evm_domain_test_runtime::{AccountId, RuntimeApi};
+use futures::executor::block_on;
+use jsonrpsee::RpcModule;
+use parity_scale_codec::{Decode, Encode};
+use sc_client_api::{Backend, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_rpc_server::SubscriptionIdProvider;
+use sc_transaction_pool_api::TransactionPool;
+use serde::de::DeserializeOwned;
+use sp_api::ProvideRuntimeApi;
+use sp_core::testing::TaskExecutor;
+use sp_core::traits::SpawnEssentialNamed;
+use sp_runtime::traits::Block as BlockT;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+use substrate_frame_rpc_system::AccountNonceApi;
+
+/// Provider with a single RPC method, default RPC methods need a running node
+struct TestProvider;
+
+impl<Block, Client, TxPool, BE, AccountId, CIDP>
+    RpcProvider<Block, Client, TxPool, BE, AccountId, CIDP> for TestProvider
+where
+    Block: BlockT,
+    Client: ProvideRuntimeApi<Block> + StorageProvider<Block, BE>,
+    Client::Api: AccountNonceApi<Block, AccountId, Nonce>,
+    TxPool: TransactionPool<Block = Block> + Sync + Send + 'static,
+    BE: Backend<Block> + 'static,
+    AccountId: DeserializeOwned + Encode + Debug + Decode + Display + Clone + Sync + Send + 'static,
+{
+    type Deps = ();
+
+    fn deps(
+        &self,
+        _full_deps: FullDeps<Block, Client, TxPool, BE, CIDP>,
+    ) -> Result<Self::Deps, sc_service::Error> {
+        Ok(())
+    }
+
+    fn rpc_id(&self) -> Option<Box<dyn SubscriptionIdProvider>> {
+        None
+    }
+
+    fn rpc_builder<SE>(
+        &self,
+        _deps: Self::Deps,
+        _subscription_task_executor: SubscriptionTaskExecutor,
+        _essential_task_spawner: SE,
+    ) -> Result<RpcModule<()>, Box<dyn Error + Send + Sync>>
+    where
+        SE: SpawnEssentialNamed + Clone,
+    {
+        Ok(module_with_method("base_ping"))
+    }
+}
+
+fn build_rpc_module<RpcExtensionsBuilder>(
+    provider: &RpcExtensionsProvider<TestProvider, RpcExtensionsBuilder>,
+) -> Result<RpcModule<()>, Box<dyn Error + Send + Sync>>
+where
+    RpcExtensionsBuilder:
+        Fn(SubscriptionTaskExecutor) -> Result<RpcModule<()>, Box<dyn Error + Send + Sync>>,
+{
+    let task_executor = TaskExecutor::new();
+
+    RpcProvider::<
+        Block,
+        FullClient<Block, RuntimeApi>,
+        FullPool<RuntimeApi>,
+        FullBackend<Block>,
+        AccountId,
+        (),
+    >::rpc_builder(provider, (), Arc::new(task_executor.clone()), task_executor)
+}
+
+fn module_with_method(method_name: &'static str) -> RpcModule<()> {
+    let mut module = RpcModule::new(());
+    module
+        .register_method(method_name, |_, _, _| "pong")
+        .unwrap();
+    module
+}
+
+#[test]
+fn rpc_extensions_are_callable() {
+    let base = module_with_method("base_ping");
+    let extensions = module_with_method("extension_ping");
+
+    let module = merge_rpc_extensions(base, extensions).unwrap();
+
+    for method_name in ["base_ping", "extension_ping"] {
+        let request = format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method_name}","params":[]}}"#);
+        let (response, _stream) = block_on(module.raw_json_request(&request, 1)).unwrap();
+        assert_eq!(response, r#"{"jsonrpc":"2.0","id":1,"result":"pong"}"#);
+    }
+}
+
+#[test]
+fn rpc_extensions_provider_registers_extensions() {
+    let provider = RpcExtensionsProvider::new(TestProvider, |_subscription_task_executor| {
+        Ok(module_with_method("extension_ping"))
+    });
+
+    let module = build_rpc_module(&provider).unwrap();
+
+    let method_names = module.method_names().collect::<Vec<_>>();
+    assert!(method_names.contains(&"base_ping"));
+    assert!(method_names.contains(&"extension_ping"));
+
+    let request = r#"{"jsonrpc":"2.0","id":1,"method":"extension_ping","params":[]}"#;
+    let (response, _stream) = block_on(module.raw_json_request(request, 1)).unwrap();
+    assert_eq!(response, r#"{"jsonrpc":"2.0","id":1,"result":"pong"}"#);
+
+    // Extensions can't override methods of the inner provider
+    let provider = RpcExtensionsProvider::new(TestProvider, |_subscription_task_executor| {
+        Ok(module_with_method("base_ping"))
+    });
+    assert!(build_rpc_module(&provider).is_err());
+}
+
+#[test]
+fn rpc_extensions_method_conflict() {
+    let base = module_with_method("test_ping");
+    let extensions = module_with_method("test_ping");
+
+    assert!(merge_rpc_extensions(base, extensions).is_err());
+}
@@ -8,6 +8,8 @@
 
 use domain_runtime_primitives::{Balance, Nonce};
 use jsonrpsee::RpcModule;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
 use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 use sc_client_api::{AuxStore, BlockBackend};
 use sc_network::service::traits::NetworkService;
@@ -15,11 +17,13 @@ use sc_network_sync::SyncingService;
 use sc_service::{DatabaseSource, SpawnTaskHandle};
 use sc_transaction_pool_api::TransactionPool;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_consensus::SyncOracle;
 use sp_core::{Decode, Encode};
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, NumberFor, Zero};
 use std::fmt::{Debug, Display};
 use std::sync::Arc;
 use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -66,6 +70,61 @@ impl<Block: BlockT, Client, TP, BE, CIDP: Clone> Clone for FullDeps<Block, Clien
     }
 }
 
+/// Health of the domain node, a cheap alternative to `system_health` for liveness and readiness
+/// checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainHealth<Number> {
+    /// Best block number
+    pub best_number: Number,
+    /// Number of connected peers
+    pub peers: usize,
+    /// Whether the node is performing major sync
+    pub is_syncing: bool,
+    /// Whether the node is ready: it is not syncing and has imported at least one block beyond
+    /// genesis
+    pub is_ready: bool,
+}
+
+/// Domain node health RPC API.
+#[rpc(server)]
+pub trait DomainHealthApi<Number> {
+    /// Get health of the domain node
+    #[method(name = "domain_health")]
+    fn health(&self) -> RpcResult<DomainHealth<Number>>;
+}
+
+/// Implementation of [`DomainHealthApiServer`].
+pub struct DomainHealthRpc<Block: BlockT, Client> {
+    client: Arc<Client>,
+    sync: Arc<SyncingService<Block>>,
+}
+
+impl<Block: BlockT, Client> DomainHealthRpc<Block, Client> {
+    /// Create new instance
+    pub fn new(client: Arc<Client>, sync: Arc<SyncingService<Block>>) -> Self {
+        Self { client, sync }
+    }
+}
+
+impl<Block, Client> DomainHealthApiServer<NumberFor<Block>> for DomainHealthRpc<Block, Client>
+where
+    Block: BlockT,
+    Client: HeaderBackend<Block> + Send + Sync + 'static,
+{
+    fn health(&self) -> RpcResult<DomainHealth<NumberFor<Block>>> {
+        let best_number = self.client.info().best_number;
+        let is_syncing = self.sync.is_major_syncing();
+
+        Ok(DomainHealth {
+            best_number,
+            peers: self.sync.num_connected_peers(),
+            is_syncing,
+            is_ready: !is_syncing && !best_number.is_zero(),
+        })
+    }
+}
+
 /// Instantiate all RPC extensions.
 pub fn create_full<Block, Client, P, AccountId, BE, CIDP>(
     deps: FullDeps<Block, Client, P, BE, CIDP>,
@@ -87,10 +146,13 @@ where
     AccountId: DeserializeOwned + Encode + Debug + Decode + Display + Clone + Sync + Send + 'static,
 {
     let mut module = RpcModule::new(());
-    let FullDeps { client, pool, .. } = deps;
+    let FullDeps {
+        client, pool, sync, ..
+    } = deps;
 
     module.merge(System::new(client.clone(), pool).into_rpc())?;
-    module.merge(TransactionPayment::new(client).into_rpc())?;
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(DomainHealthRpc::new(client, sync).into_rpc())?;
 
     Ok(module)
 }
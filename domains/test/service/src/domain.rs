@@ -268,6 +268,8 @@ where
             challenge_period: domain_block_pruning_depth,
             consensus_chain_sync_params: None::<ConsensusChainSyncParams<_, HeaderFor<Block>>>,
             domain_backend,
+            telemetry_buffer_size: None,
+            transaction_pool_limits: None,
         };
 
         let domain_node = domain_service::new_full::<
@@ -759,6 +761,29 @@ impl DomainNodeBuilder {
         key: Sr25519Keyring,
         mock_consensus_node: &mut MockConsensusNode,
     ) -> AutoIdDomainNode {
+        self.build_auto_id_node_with_provider(role, key, mock_consensus_node, DefaultProvider)
+            .await
+    }
+
+    /// Build an Auto ID domain node with custom block import and RPC provider
+    pub async fn build_auto_id_node_with_provider<Provider>(
+        self,
+        role: Role,
+        key: Sr25519Keyring,
+        mock_consensus_node: &mut MockConsensusNode,
+        provider: Provider,
+    ) -> AutoIdDomainNode
+    where
+        Provider: domain_service::providers::BlockImportProvider<Block, AutoIdDomainClient>
+            + domain_service::providers::RpcProvider<
+                Block,
+                AutoIdDomainClient,
+                FullPool<auto_id_domain_test_runtime::RuntimeApi>,
+                Backend,
+                auto_id_domain_test_runtime::AccountId,
+                CreateInherentDataProvider<subspace_test_client::Client, CBlock>,
+            > + 'static,
+    {
         DomainNode::build(
             AUTO_ID_DOMAIN_ID,
             self.tokio_handle,
@@ -776,7 +801,7 @@ impl DomainNodeBuilder {
             mock_consensus_node,
             self.rpc_addr,
             self.rpc_port,
-            DefaultProvider,
+            provider,
         )
         .await
     }
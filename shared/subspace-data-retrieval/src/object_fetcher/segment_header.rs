@@ -47,20 +47,32 @@ pub fn min_segment_header_encoded_size() -> usize {
     min_segment_header.encoded_size()
 }
 
-/// The maximum size of the segment header.
+/// The maximum size of the segment header, across all segment header versions.
 #[inline]
 pub fn max_segment_header_encoded_size() -> usize {
-    let max_segment_header = SegmentHeader::V0 {
-        segment_index: u64::MAX.into(),
+    let segment_index = u64::MAX.into();
+    let last_archived_block = LastArchivedBlock {
+        number: u32::MAX,
+        archived_progress: ArchivedBlockProgress::Partial(u32::MAX),
+    };
+
+    let max_segment_header_v0 = SegmentHeader::V0 {
+        segment_index,
         segment_commitment: SegmentCommitment::default(),
         prev_segment_header_hash: Blake3Hash::default(),
-        last_archived_block: LastArchivedBlock {
-            number: u32::MAX,
-            archived_progress: ArchivedBlockProgress::Partial(u32::MAX),
-        },
+        last_archived_block,
+    };
+    let max_segment_header_v1 = SegmentHeader::V1 {
+        segment_index,
+        segment_commitment: SegmentCommitment::default(),
+        prev_segment_header_hash: Blake3Hash::default(),
+        last_archived_block,
+        record_count: u32::MAX,
     };
 
-    max_segment_header.encoded_size()
+    max_segment_header_v0
+        .encoded_size()
+        .max(max_segment_header_v1.encoded_size())
 }
 
 /// Removes the segment header from the start of a piece, and returns the remaining data.
@@ -178,6 +190,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn max_segment_header_v1_fits() {
+        let segment_header = SegmentHeader::V1 {
+            segment_index: u64::MAX.into(),
+            segment_commitment: SegmentCommitment::default(),
+            prev_segment_header_hash: Blake3Hash::default(),
+            last_archived_block: LastArchivedBlock {
+                number: u32::MAX,
+                archived_progress: ArchivedBlockProgress::Partial(u32::MAX),
+            },
+            record_count: u32::MAX,
+        };
+
+        assert!(segment_header.encoded_size() <= max_segment_header_encoded_size());
+    }
+
     #[test]
     fn segment_version_variant_constant() {
         let segment = Segment::V0 { items: Vec::new() };
@@ -59,6 +59,41 @@ fn criterion_benchmark(c: &mut Criterion) {
             })
         });
     }
+
+    {
+        let polynomial = kzg.poly(&values).unwrap();
+        let commitment = kzg.commit(&polynomial).unwrap();
+        let openings = (0..16)
+            .map(|index| {
+                let witness = kzg.create_witness(&polynomial, num_values, index).unwrap();
+                (index, values[index as usize], witness)
+            })
+            .collect::<Vec<_>>();
+
+        c.bench_function("verify-16-sequential", |b| {
+            b.iter(|| {
+                for (index, value, witness) in &openings {
+                    kzg.verify(
+                        black_box(&commitment),
+                        black_box(num_values),
+                        black_box(*index),
+                        black_box(value),
+                        black_box(witness),
+                    );
+                }
+            })
+        });
+
+        c.bench_function("verify-16-batch", |b| {
+            b.iter(|| {
+                kzg.verify_batch(
+                    black_box(&commitment),
+                    black_box(num_values),
+                    black_box(&openings),
+                );
+            })
+        });
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
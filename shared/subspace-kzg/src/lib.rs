@@ -15,9 +15,10 @@ use alloc::vec::Vec;
 use core::mem;
 use derive_more::{AsMut, AsRef, Deref, DerefMut, From, Into};
 use kzg::eip_4844::{BYTES_PER_G1, BYTES_PER_G2};
-use kzg::{FFTFr, FFTSettings, Fr, G1, G2, KZGSettings};
+use kzg::{FFTFr, FFTSettings, Fr, G1, G1Mul, G2, KZGSettings};
 #[cfg(feature = "std")]
 use parking_lot::Mutex;
+use rust_kzg_blst::kzg_proofs::pairings_verify;
 use rust_kzg_blst::types::fft_settings::FsFFTSettings;
 use rust_kzg_blst::types::fr::FsFr;
 use rust_kzg_blst::types::g1::FsG1;
@@ -28,9 +29,11 @@ use rust_kzg_blst::types::poly::FsPoly;
 use spin::Mutex;
 use static_assertions::const_assert_eq;
 use subspace_core_primitives::ScalarBytes;
-use subspace_core_primitives::pieces::{RecordCommitment, RecordWitness};
+use subspace_core_primitives::hashes::{Blake3Hasher, blake3_254_hash_to_scalar};
+use subspace_core_primitives::pieces::{RawRecord, Record, RecordCommitment, RecordWitness};
 use subspace_core_primitives::segments::SegmentCommitment;
 use subspace_core_primitives::solutions::ChunkWitness;
+use subtle::{Choice, ConstantTimeEq};
 use tracing::debug;
 
 /// Embedded KZG settings as bytes, too big for `no_std` in most cases
@@ -44,6 +47,9 @@ pub const NUM_G1_POWERS: usize = 32_768;
 /// Number of G2 powers stored in [`EMBEDDED_KZG_SETTINGS_BYTES`]
 pub const NUM_G2_POWERS: usize = 65;
 
+/// Size of the header of KZG setup accepted by [`Kzg::from_setup_bytes()`]
+const SETUP_HEADER_SIZE: usize = size_of::<u32>() * 2;
+
 // Symmetric function is present in tests
 /// Function turns bytes into `FsKZGSettings`, it is up to the user to ensure that bytes make sense,
 /// otherwise result can be very wrong (but will not panic).
@@ -86,6 +92,38 @@ fn bytes_to_kzg_settings(
     })
 }
 
+/// Errors that can happen when loading KZG setup
+#[derive(Debug, thiserror::Error)]
+pub enum KzgError {
+    /// I/O error
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Setup is too short to contain header
+    #[error("Setup is too short to contain header: {length} bytes")]
+    TooShort {
+        /// Length of the setup in bytes
+        length: usize,
+    },
+    /// Invalid number of G1 powers
+    #[error("Invalid number of G1 powers {0}, must be a power of two larger than 1")]
+    InvalidG1Powers(u32),
+    /// Invalid number of G2 powers
+    #[error("Invalid number of G2 powers {0}, must be at least 2")]
+    InvalidG2Powers(u32),
+    /// Setup length doesn't match number of powers in the header
+    #[error("Invalid setup length: expected {expected} bytes, actual {actual} bytes")]
+    InvalidLength {
+        /// Expected length in bytes
+        expected: usize,
+        /// Actual length in bytes
+        actual: usize,
+    },
+    /// Invalid G1 or G2 point
+    #[error("Invalid point: {0}")]
+    InvalidPoint(String),
+}
+
 /// Commitment to polynomial
 #[derive(Debug, Clone, From)]
 pub struct Polynomial(FsPoly);
@@ -106,11 +144,46 @@ impl Polynomial {
     }
 }
 
+/// Errors that can happen when converting bytes into [`Scalar`]
+#[derive(Debug, thiserror::Error)]
+pub enum ScalarError {
+    /// Invalid number of safe bytes
+    #[error("Invalid number of safe bytes: expected {expected}, actual {actual}")]
+    InvalidSafeBytesLength {
+        /// Expected number of bytes
+        expected: usize,
+        /// Actual number of bytes
+        actual: usize,
+    },
+    /// Bytes do not represent a valid scalar
+    #[error("Invalid scalar: {0}")]
+    InvalidScalar(String),
+    /// String is not valid hex of [`ScalarBytes::FULL_BYTES`] bytes
+    #[error("Invalid hex: {0}")]
+    InvalidHex(hex::FromHexError),
+}
+
 /// Representation of a single BLS12-381 scalar value.
-#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Deref, DerefMut)]
+///
+/// Equality comparison is constant-time, see [`ConstantTimeEq`].
+#[derive(Debug, Default, Copy, Clone, Eq, Deref, DerefMut)]
 #[repr(transparent)]
 pub struct Scalar(FsFr);
 
+impl ConstantTimeEq for Scalar {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.to_bytes().ct_eq(&other.to_bytes())
+    }
+}
+
+impl PartialEq for Scalar {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
 const_assert_eq!(
     mem::size_of::<Option<Scalar>>(),
     mem::size_of::<Option<FsFr>>()
@@ -206,6 +279,68 @@ impl Scalar {
         self.into()
     }
 
+    /// Convert scalar into hex string of big-endian bytes
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Create scalar from hex string of big-endian bytes, returns error if string is not valid hex,
+    /// has wrong length or doesn't represent a valid scalar
+    pub fn from_hex(hex: &str) -> Result<Self, ScalarError> {
+        let mut bytes = [0u8; ScalarBytes::FULL_BYTES];
+        hex::decode_to_slice(hex, &mut bytes).map_err(ScalarError::InvalidHex)?;
+        Self::try_from(bytes).map_err(ScalarError::InvalidScalar)
+    }
+
+    /// Create scalar from [`ScalarBytes::SAFE_BYTES`] bytes.
+    ///
+    /// Safe bytes are padded with a single zero byte in front (scalar uses big-endian
+    /// representation) to form [`ScalarBytes::FULL_BYTES`] bytes, which is why the result is
+    /// always a valid scalar. Returns error if input length is not exactly
+    /// [`ScalarBytes::SAFE_BYTES`].
+    pub fn try_from_safe_bytes(bytes: &[u8]) -> Result<Self, ScalarError> {
+        let bytes = <&[u8; ScalarBytes::SAFE_BYTES]>::try_from(bytes).map_err(|_error| {
+            ScalarError::InvalidSafeBytesLength {
+                expected: ScalarBytes::SAFE_BYTES,
+                actual: bytes.len(),
+            }
+        })?;
+
+        Ok(Self::from(bytes))
+    }
+
+    /// Convert scalar into safe bytes by stripping zero padding byte in front, inverse of
+    /// [`Self::try_from_safe_bytes()`].
+    ///
+    /// # Panics
+    /// Panics if the scalar doesn't fit into [`ScalarBytes::SAFE_BYTES`] bytes (the first byte of
+    /// [`Self::to_bytes()`] is not zero), use [`Self::try_to_safe_bytes()`] for scalars that are
+    /// not guaranteed to have been created from safe bytes.
+    pub fn to_safe_bytes(&self) -> [u8; ScalarBytes::SAFE_BYTES] {
+        self.try_to_safe_bytes()
+            .expect("Scalar must have been created from safe bytes; qed")
+    }
+
+    /// Iterate over chunks of a raw record (source data) as scalars.
+    ///
+    /// Each chunk of [`ScalarBytes::SAFE_BYTES`] is zero-padded as described in
+    /// [`Self::try_from_safe_bytes()`].
+    pub fn raw_record_scalars(raw_record: &RawRecord) -> impl ExactSizeIterator<Item = Self> + '_ {
+        raw_record.iter().map(Self::from)
+    }
+
+    /// Iterate over chunks of a record as scalars.
+    ///
+    /// Record chunks are [`ScalarBytes::FULL_BYTES`] in size and are not zero-padded, so each
+    /// chunk is checked to be a valid scalar.
+    pub fn record_scalars(
+        record: &Record,
+    ) -> impl ExactSizeIterator<Item = Result<Self, ScalarError>> + '_ {
+        record
+            .iter()
+            .map(|chunk| Self::try_from(chunk).map_err(ScalarError::InvalidScalar))
+    }
+
     /// Convert scalar into safe bytes, returns `None` if not possible to convert due to larger
     /// internal value
     pub fn try_to_safe_bytes(&self) -> Option<[u8; ScalarBytes::SAFE_BYTES]> {
@@ -746,6 +881,66 @@ impl Kzg {
         Self { inner }
     }
 
+    /// Create new instance from KZG setup stored in a file, see [`Self::from_setup_bytes()`] for
+    /// format description.
+    #[cfg(feature = "std")]
+    pub fn from_setup_file(path: &std::path::Path) -> Result<Self, KzgError> {
+        let bytes = std::fs::read(path)?;
+        Self::from_setup_bytes(&bytes)
+    }
+
+    /// Create new instance from KZG setup bytes.
+    ///
+    /// Setup starts with the number of G1 powers and the number of G2 powers, both as
+    /// little-endian `u32`, followed by compressed G1 powers and compressed G2 powers respectively.
+    /// Number of G1 powers must be a power of two larger than 1 and there must be at least 2 G2
+    /// powers.
+    pub fn from_setup_bytes(bytes: &[u8]) -> Result<Self, KzgError> {
+        let Some((header, powers)) = bytes.split_first_chunk::<{ SETUP_HEADER_SIZE }>() else {
+            return Err(KzgError::TooShort {
+                length: bytes.len(),
+            });
+        };
+        let (num_g1_powers, num_g2_powers) = header.split_at(size_of::<u32>());
+        let num_g1_powers = u32::from_le_bytes(
+            num_g1_powers
+                .try_into()
+                .expect("Header contains two u32 values; qed"),
+        );
+        let num_g2_powers = u32::from_le_bytes(
+            num_g2_powers
+                .try_into()
+                .expect("Header contains two u32 values; qed"),
+        );
+
+        if num_g1_powers < 2 || !num_g1_powers.is_power_of_two() {
+            return Err(KzgError::InvalidG1Powers(num_g1_powers));
+        }
+        if num_g2_powers < 2 {
+            return Err(KzgError::InvalidG2Powers(num_g2_powers));
+        }
+
+        let num_g1_powers = num_g1_powers as usize;
+        let num_g2_powers = num_g2_powers as usize;
+        let expected = BYTES_PER_G1 * num_g1_powers + BYTES_PER_G2 * num_g2_powers;
+        if powers.len() != expected {
+            return Err(KzgError::InvalidLength {
+                expected: SETUP_HEADER_SIZE + expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let kzg_settings = bytes_to_kzg_settings(powers, num_g1_powers, num_g2_powers)
+            .map_err(KzgError::InvalidPoint)?;
+
+        let inner = Arc::new(Inner {
+            kzg_settings,
+            fft_settings_cache: Mutex::default(),
+        });
+
+        Ok(Self { inner })
+    }
+
     /// Create polynomial from data. Data must be multiple of 32 bytes, each containing up to 254
     /// bits of information.
     ///
@@ -815,6 +1010,82 @@ impl Kzg {
         }
     }
 
+    /// Verifies multiple openings of the polynomial created from `num_values` values matching the
+    /// `commitment` at once.
+    ///
+    /// Each opening is a tuple of index, value at that index and corresponding witness. Openings are
+    /// combined using random linear combination with coefficients derived from all inputs, such
+    /// that the whole batch costs a single pairing check. Returns `false` if any of the openings is
+    /// invalid and `true` if there are no openings.
+    pub fn verify_batch(
+        &self,
+        commitment: &Commitment,
+        num_values: usize,
+        openings: &[(u32, Scalar, Witness)],
+    ) -> bool {
+        if openings.is_empty() {
+            return true;
+        }
+
+        let fft_settings = match self.get_fft_settings(num_values) {
+            Ok(fft_settings) => fft_settings,
+            Err(error) => {
+                debug!(error, "Failed to derive fft settings");
+                return false;
+            }
+        };
+
+        // Bind random coefficients to all inputs, so that prover can't choose witnesses that
+        // cancel each other out
+        let transcript = {
+            let mut hasher = Blake3Hasher::new();
+            hasher
+                .update(&commitment.to_bytes())
+                .update(&(num_values as u64).to_le_bytes());
+            for (index, value, witness) in openings {
+                hasher
+                    .update(&index.to_le_bytes())
+                    .update(&value.to_bytes())
+                    .update(&witness.to_bytes());
+            }
+            hasher.finalize()
+        };
+
+        // For every opening `e(C - [y]G1, G2) == e(W, [s - x]G2)`, which is equivalent to
+        // `e(C - [y]G1 + [x]W, G2) == e(W, [s]G2)`. Combined with random coefficients `r` it
+        // becomes `e([sum(r)]C - [sum(r * y)]G1 + sum([r * x]W), G2) == e(sum([r]W), [s]G2)`.
+        let mut r_sum = FsFr::zero();
+        let mut r_y_sum = FsFr::zero();
+        let mut r_x_w_sum = FsG1::identity();
+        let mut r_w_sum = FsG1::identity();
+        for (opening_index, (index, value, witness)) in openings.iter().enumerate() {
+            let r = Scalar::try_from(blake3_254_hash_to_scalar(
+                &[transcript.as_ref(), &(opening_index as u64).to_le_bytes()].concat(),
+            ))
+            .expect("254 bits always fit into scalar; qed")
+            .0;
+            let x = fft_settings.get_expanded_roots_of_unity_at(*index as usize);
+
+            r_sum = r_sum.add(&r);
+            r_y_sum = r_y_sum.add(&r.mul(&value.0));
+            r_x_w_sum = r_x_w_sum.add_or_dbl(&witness.0.mul(&r.mul(&x)));
+            r_w_sum = r_w_sum.add_or_dbl(&witness.0.mul(&r));
+        }
+
+        let lhs = commitment
+            .0
+            .mul(&r_sum)
+            .sub(&FsG1::generator().mul(&r_y_sum))
+            .add_or_dbl(&r_x_w_sum);
+
+        pairings_verify(
+            &lhs,
+            &FsG2::generator(),
+            &r_w_sum,
+            &self.inner.kzg_settings.secret_g2[1],
+        )
+    }
+
     /// Get FFT settings for specified number of values, uses internal cache to avoid derivation
     /// every time.
     fn get_fft_settings(&self, num_values: usize) -> Result<Arc<FsFFTSettings>, String> {
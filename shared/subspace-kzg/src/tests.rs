@@ -1,7 +1,13 @@
-use crate::{Kzg, Scalar};
+use crate::{
+    Commitment, EMBEDDED_KZG_SETTINGS_BYTES, Kzg, KzgError, NUM_G1_POWERS, Scalar, ScalarError,
+};
+use kzg::eip_4844::{BYTES_PER_G1, BYTES_PER_G2};
+use parity_scale_codec::{Decode, Encode};
 use rand::thread_rng;
 use rand_core::RngCore;
 use subspace_core_primitives::ScalarBytes;
+use subspace_core_primitives::pieces::{RawRecord, Record};
+use subspace_core_primitives::segments::SegmentCommitment;
 
 #[test]
 fn basic() {
@@ -82,3 +88,206 @@ fn bytes_scalars_conversion() {
         }
     }
 }
+
+#[test]
+fn segment_commitment_encoding() {
+    let values = (0..8)
+        .map(|_| Scalar::from(rand::random::<[u8; ScalarBytes::SAFE_BYTES]>()))
+        .collect::<Vec<_>>();
+
+    let kzg = Kzg::new();
+    let polynomial = kzg.poly(&values).unwrap();
+    let commitment = kzg.commit(&polynomial).unwrap();
+
+    let segment_commitment = SegmentCommitment::from(commitment);
+
+    // Encoding must be byte-identical to the underlying commitment for compatibility with data
+    // created before the newtype was introduced
+    let encoded = segment_commitment.encode();
+    assert_eq!(encoded, commitment.to_bytes());
+    assert_eq!(
+        SegmentCommitment::decode(&mut encoded.as_slice()).unwrap(),
+        segment_commitment
+    );
+    assert_eq!(
+        Commitment::try_from(&segment_commitment).unwrap(),
+        commitment
+    );
+}
+
+#[test]
+fn verify_batch() {
+    let values = (0..8)
+        .map(|_| Scalar::from(rand::random::<[u8; ScalarBytes::SAFE_BYTES]>()))
+        .collect::<Vec<_>>();
+
+    let kzg = Kzg::new();
+    let polynomial = kzg.poly(&values).unwrap();
+    let commitment = kzg.commit(&polynomial).unwrap();
+
+    let num_values = values.len();
+
+    let mut openings = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let index = index.try_into().unwrap();
+            let witness = kzg.create_witness(&polynomial, num_values, index).unwrap();
+
+            (index, *value, witness)
+        })
+        .collect::<Vec<_>>();
+
+    assert!(kzg.verify_batch(&commitment, num_values, &openings));
+    assert!(kzg.verify_batch(&commitment, num_values, &openings[..1]));
+    assert!(kzg.verify_batch(&commitment, num_values, &[]));
+
+    // Wrong value
+    {
+        let mut openings = openings.clone();
+        openings[3].1 = values[4];
+        assert!(!kzg.verify_batch(&commitment, num_values, &openings));
+    }
+
+    // Tampered witness
+    openings[5].2 = openings[6].2;
+    assert!(!kzg.verify_batch(&commitment, num_values, &openings));
+}
+
+#[test]
+fn setup_file_round_trip() {
+    let num_g1_powers = 16_u32;
+    let num_g2_powers = 2_u32;
+
+    // Prefix of the embedded setup is a valid setup with fewer powers
+    let mut setup = Vec::new();
+    setup.extend_from_slice(&num_g1_powers.to_le_bytes());
+    setup.extend_from_slice(&num_g2_powers.to_le_bytes());
+    setup.extend_from_slice(&EMBEDDED_KZG_SETTINGS_BYTES[..BYTES_PER_G1 * num_g1_powers as usize]);
+    setup.extend_from_slice(
+        &EMBEDDED_KZG_SETTINGS_BYTES[BYTES_PER_G1 * NUM_G1_POWERS..]
+            [..BYTES_PER_G2 * num_g2_powers as usize],
+    );
+
+    let setup_file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(setup_file.path(), &setup).unwrap();
+    let kzg = Kzg::from_setup_file(setup_file.path()).unwrap();
+
+    let values = (0..8)
+        .map(|_| Scalar::from(rand::random::<[u8; ScalarBytes::SAFE_BYTES]>()))
+        .collect::<Vec<_>>();
+    let polynomial = kzg.poly(&values).unwrap();
+    let commitment = kzg.commit(&polynomial).unwrap();
+    let witness = kzg.create_witness(&polynomial, values.len(), 3).unwrap();
+    assert!(kzg.verify(&commitment, values.len(), 3, &values[3], &witness));
+
+    // Same powers as embedded setup result in the same commitment
+    let embedded_kzg = Kzg::new();
+    assert_eq!(
+        embedded_kzg
+            .commit(&embedded_kzg.poly(&values).unwrap())
+            .unwrap(),
+        commitment
+    );
+
+    assert!(matches!(
+        Kzg::from_setup_bytes(&setup[..setup.len() - 1]),
+        Err(KzgError::InvalidLength { .. })
+    ));
+    assert!(matches!(
+        Kzg::from_setup_bytes(&setup[..4]),
+        Err(KzgError::TooShort { length: 4 })
+    ));
+
+    let mut invalid_g1_powers = setup.clone();
+    invalid_g1_powers[..4].copy_from_slice(&15_u32.to_le_bytes());
+    assert!(matches!(
+        Kzg::from_setup_bytes(&invalid_g1_powers),
+        Err(KzgError::InvalidG1Powers(15))
+    ));
+
+    assert!(matches!(
+        Kzg::from_setup_file(&setup_file.path().with_extension("missing")),
+        Err(KzgError::Io(_))
+    ));
+}
+
+#[test]
+fn scalar_hex_and_equality() {
+    let scalar = Scalar::from(rand::random::<[u8; ScalarBytes::SAFE_BYTES]>());
+
+    let hex = scalar.to_hex();
+    assert_eq!(hex.len(), ScalarBytes::FULL_BYTES * 2);
+    assert_eq!(Scalar::from_hex(&hex).unwrap(), scalar);
+
+    // Wrong length
+    assert!(matches!(
+        Scalar::from_hex(&hex[2..]),
+        Err(ScalarError::InvalidHex(_))
+    ));
+    // Not hex
+    assert!(matches!(
+        Scalar::from_hex(&"zz".repeat(ScalarBytes::FULL_BYTES)),
+        Err(ScalarError::InvalidHex(_))
+    ));
+    // Not a valid scalar
+    assert!(matches!(
+        Scalar::from_hex(&"ff".repeat(ScalarBytes::FULL_BYTES)),
+        Err(ScalarError::InvalidScalar(_))
+    ));
+
+    let mut other_bytes = scalar.to_bytes();
+    other_bytes[ScalarBytes::FULL_BYTES - 1] ^= 1;
+    let other = Scalar::try_from(other_bytes).unwrap();
+    assert_eq!(scalar, scalar);
+    assert_ne!(scalar, other);
+}
+
+#[test]
+fn scalar_safe_bytes_round_trip() {
+    let safe_bytes = rand::random::<[u8; ScalarBytes::SAFE_BYTES]>();
+    let scalar = Scalar::try_from_safe_bytes(&safe_bytes).unwrap();
+    // Safe bytes are padded with a zero byte in front
+    assert_eq!(scalar.to_bytes()[0], 0);
+    assert_eq!(scalar.to_bytes()[1..], safe_bytes);
+    assert_eq!(scalar.to_safe_bytes(), safe_bytes);
+
+    assert!(matches!(
+        Scalar::try_from_safe_bytes(&[0; ScalarBytes::SAFE_BYTES - 1]),
+        Err(ScalarError::InvalidSafeBytesLength {
+            expected: ScalarBytes::SAFE_BYTES,
+            actual
+        }) if actual == ScalarBytes::SAFE_BYTES - 1
+    ));
+    assert!(matches!(
+        Scalar::try_from_safe_bytes(&[0; ScalarBytes::FULL_BYTES]),
+        Err(ScalarError::InvalidSafeBytesLength { .. })
+    ));
+
+    let mut raw_record = RawRecord::new_boxed();
+    raw_record.as_flattened_mut().iter_mut().for_each(|byte| {
+        *byte = rand::random();
+    });
+    let scalars = Scalar::raw_record_scalars(&raw_record).collect::<Vec<_>>();
+    assert_eq!(scalars.len(), RawRecord::NUM_CHUNKS);
+    for (scalar, chunk) in scalars.iter().zip(raw_record.iter()) {
+        assert_eq!(&scalar.to_safe_bytes(), chunk);
+    }
+
+    let mut record = Record::new_boxed();
+    record
+        .iter_mut()
+        .zip(&scalars)
+        .for_each(|(chunk, scalar)| *chunk = scalar.to_bytes());
+    let record_scalars = Scalar::record_scalars(&record)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(record_scalars, scalars);
+
+    // Value that doesn't fit into the scalar field
+    record[0] = [0xff; ScalarBytes::FULL_BYTES];
+    assert!(matches!(
+        Scalar::record_scalars(&record).next(),
+        Some(Err(ScalarError::InvalidScalar(_)))
+    ));
+}